@@ -57,19 +57,23 @@ async fn main() {
         Command::Power => client
             .power()
             .await
+            .map(drop)
             .with_context(|| "Failed to switch power".to_string()),
         Command::Play => client
             .play()
             .await
+            .map(drop)
             .with_context(|| "Failed to send play".to_string()),
         Command::Pause => client
             .pause()
             .await
+            .map(drop)
             .with_context(|| "Failed to send pause".to_string()),
         Command::Volume(volume_args) => match volume_args.value {
             Some(volume) => client
                 .set_volume(volume)
                 .await
+                .map(drop)
                 .with_context(|| "Failed set volume".to_string()),
             None => print_volume(&client).await,
         },
@@ -77,6 +81,7 @@ async fn main() {
             Some(preset) => client
                 .set_preset(preset)
                 .await
+                .map(drop)
                 .with_context(|| "Failed to change preset".to_string()),
             None => print_presets(&client).await,
         },
@@ -105,7 +110,9 @@ async fn print_presets(client: &BoseClient) -> Result<()> {
     for preset in presets.items {
         println!(
             "{} - {} ({})",
-            preset.id, preset.content_item.name, preset.content_item.source
+            preset.id,
+            preset.content_item.name.unwrap_or_default(),
+            preset.content_item.source
         )
     }
     Ok(())
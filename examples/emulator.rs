@@ -0,0 +1,25 @@
+//! Runs a local SoundTouch emulator on the default ports
+//!
+//! ```sh
+//! cargo run --example emulator --features emulator
+//! cargo run --example bose -- --hostname 127.0.0.1 status
+//! ```
+use bose_soundtouch::{Emulator, FakeState, DEFAULT_PORT, DEFAULT_WEBSOCKET_PORT};
+
+#[tokio::main]
+async fn main() -> bose_soundtouch::Result<()> {
+    let emulator = Emulator::bind(
+        ("0.0.0.0", DEFAULT_PORT),
+        ("0.0.0.0", DEFAULT_WEBSOCKET_PORT),
+        FakeState::default(),
+    )
+    .await?;
+
+    println!(
+        "SoundTouch emulator listening on http://{} and ws://{}",
+        emulator.http_addr(),
+        emulator.websocket_addr()
+    );
+    std::future::pending::<()>().await;
+    Ok(())
+}
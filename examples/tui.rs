@@ -158,9 +158,13 @@ impl App {
 
                 // Update preset art URLs
                 for preset in &presets.items {
-                    if !preset.content_item.container_art.is_empty() {
-                        self.preset_art_urls
-                            .insert(preset.id, preset.content_item.container_art.clone());
+                    if let Some(url) = preset
+                        .content_item
+                        .container_art
+                        .as_ref()
+                        .filter(|url| !url.is_empty())
+                    {
+                        self.preset_art_urls.insert(preset.id, url.clone());
                     }
                 }
 
@@ -226,7 +230,7 @@ impl App {
                         ListItem::new(vec![Line::from(vec![
                             Span::raw(format!("#{} ", preset.id)),
                             Span::styled(
-                                &preset.content_item.name,
+                                preset.content_item.name.as_deref().unwrap_or_default(),
                                 Style::default().fg(Color::Yellow),
                             ),
                         ])])
@@ -315,6 +319,7 @@ fn rect_to_screen_coords(rect: ratatui::layout::Rect) -> (u16, i16, u16, u16) {
 }
 
 #[cfg(feature = "websocket")]
+#[allow(clippy::single_match, clippy::manual_range_contains)]
 async fn run_app(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     app: &mut App,
@@ -392,7 +397,13 @@ async fn run_app(
                         .iter()
                         .filter_map(|preset| {
                             app.preset_art_urls.get(&preset.id).map(|url| {
-                                (preset.id, (url.clone(), preset.content_item.name.clone()))
+                                (
+                                    preset.id,
+                                    (
+                                        url.clone(),
+                                        preset.content_item.name.clone().unwrap_or_default(),
+                                    ),
+                                )
                             })
                         })
                         .collect()
@@ -438,31 +449,15 @@ async fn run_app(
                             KeyCode::Char('q') => return Ok(()),
                             KeyCode::Up => {
                                 tracing::debug!("Up arrow pressed - sending volume up command");
-                                if let Ok(volume) = app.client.get_volume().await {
-                                    let new_volume = (volume.actual + 1).min(100);
-                                    tracing::debug!(
-                                        "Setting volume from {} to {}",
-                                        volume.actual,
-                                        new_volume
-                                    );
-                                    if let Err(e) = app.client.set_volume(new_volume).await {
-                                        tracing::error!("Error setting volume: {}", e);
-                                    }
+                                if let Err(e) = app.client.volume_up(1).await {
+                                    tracing::error!("Error setting volume: {}", e);
                                 }
                                 sleep(Duration::from_millis(50)).await;
                             }
                             KeyCode::Down => {
                                 tracing::debug!("Down arrow pressed - sending volume down command");
-                                if let Ok(volume) = app.client.get_volume().await {
-                                    let new_volume = volume.actual.saturating_sub(1);
-                                    tracing::debug!(
-                                        "Setting volume from {} to {}",
-                                        volume.actual,
-                                        new_volume
-                                    );
-                                    if let Err(e) = app.client.set_volume(new_volume).await {
-                                        tracing::error!("Error setting volume: {}", e);
-                                    }
+                                if let Err(e) = app.client.volume_down(1).await {
+                                    tracing::error!("Error setting volume: {}", e);
                                 }
                                 sleep(Duration::from_millis(50)).await;
                             }
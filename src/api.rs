@@ -0,0 +1,345 @@
+//! Trait abstracting the operations of a SoundTouch device
+
+use crate::{
+    diff_presets, validate_preset_id, Bass, BassCapabilities, BoseClient, ContentItem, DeviceInfo,
+    ImportOptions, KeyValue, NowPlaying, PresetBackup, Presets, Recents, RepeatMode, Result,
+    ShuffleMode, SlotAction, SlotDiff, Sources, Status, Volume, VolumeLevel, Zone,
+};
+use std::future::Future;
+
+/// Operations supported by a SoundTouch device
+///
+/// [`BoseClient`] implements this trait by talking to a real device. Code that is
+/// generic over `SoundTouchApi` can be tested against an in-memory fake instead
+/// (see `FakeSoundTouch`, available with the `fake` feature).
+///
+/// # Example
+/// ```no_run
+/// use bose_soundtouch::{BoseClient, Result, SoundTouchApi};
+///
+/// async fn party_mode(speaker: &impl SoundTouchApi) -> Result<()> {
+///     speaker.set_preset(1).await?;
+///     speaker.set_volume(40).await?;
+///     Ok(())
+/// }
+///
+/// # async fn example() -> Result<()> {
+/// party_mode(&BoseClient::new_from_string("192.168.1.143")).await
+/// # }
+/// ```
+pub trait SoundTouchApi: Send + Sync {
+    /// Gets information about the device
+    fn get_info(&self) -> impl Future<Output = Result<DeviceInfo>> + Send;
+
+    /// Sets the device name
+    fn set_name(&self, name: &str) -> impl Future<Output = Result<Status>> + Send;
+
+    /// Gets the current playback status
+    fn get_status(&self) -> impl Future<Output = Result<NowPlaying>> + Send;
+
+    /// Gets information about the current track
+    fn get_track_info(&self) -> impl Future<Output = Result<NowPlaying>> + Send;
+
+    /// Gets the current volume settings
+    fn get_volume(&self) -> impl Future<Output = Result<Volume>> + Send;
+
+    /// Sets the volume level (0-100)
+    fn set_volume(&self, value: i32) -> impl Future<Output = Result<Status>> + Send;
+
+    /// Raises the volume by `step` levels and returns the new level
+    ///
+    /// Implementations read and set the level atomically, so that concurrent
+    /// steps are not lost, and return the level that was actually set.
+    fn volume_up(&self, step: u8) -> impl Future<Output = Result<VolumeLevel>> + Send;
+
+    /// Lowers the volume by `step` levels and returns the new level
+    ///
+    /// Atomic like [`volume_up`](Self::volume_up).
+    fn volume_down(&self, step: u8) -> impl Future<Output = Result<VolumeLevel>> + Send;
+
+    /// Gets the list of presets
+    fn get_presets(&self) -> impl Future<Output = Result<Presets>> + Send;
+
+    /// Gets the list of recently played items
+    fn get_recents(&self) -> impl Future<Output = Result<Recents>> + Send;
+
+    /// Selects a preset (1-6)
+    fn set_preset(&self, value: i32) -> impl Future<Output = Result<Status>> + Send;
+
+    /// Stores a content item in a preset slot (1-6) and returns the updated presets
+    fn store_preset(
+        &self,
+        id: i32,
+        content_item: &ContentItem,
+    ) -> impl Future<Output = Result<Presets>> + Send;
+
+    /// Clears a preset slot (1-6) and returns the updated presets
+    fn remove_preset(&self, id: i32) -> impl Future<Output = Result<Presets>> + Send;
+
+    /// Swaps the contents of two preset slots (1-6) and returns the updated presets
+    ///
+    /// Swapping with an empty slot moves the preset there.
+    fn swap_presets(&self, a: i32, b: i32) -> impl Future<Output = Result<Presets>> + Send {
+        async move {
+            validate_preset_id(a)?;
+            validate_preset_id(b)?;
+            let mut presets = self.get_presets().await?;
+            let content = |id: i32| {
+                presets
+                    .items
+                    .iter()
+                    .find(|preset| preset.id == id)
+                    .map(|preset| preset.content_item.clone())
+            };
+            let (first, second) = (content(a), content(b));
+            if first == second {
+                return Ok(presets);
+            }
+            for (id, content_item) in [(a, second), (b, first)] {
+                presets = match content_item {
+                    Some(content_item) => self.store_preset(id, &content_item).await?,
+                    None => self.remove_preset(id).await?,
+                };
+            }
+            Ok(presets)
+        }
+    }
+
+    /// Exports the presets to a portable backup
+    fn export_presets(&self) -> impl Future<Output = Result<PresetBackup>> + Send {
+        async move {
+            let info = self.get_info().await?;
+            let presets = self.get_presets().await?;
+            Ok(PresetBackup::new(&presets).with_device(&info))
+        }
+    }
+
+    /// Applies a preset backup, changing only the slots that differ
+    ///
+    /// Returns the difference of every slot; with a dry run nothing is changed.
+    fn import_presets(
+        &self,
+        backup: &PresetBackup,
+        options: &ImportOptions,
+    ) -> impl Future<Output = Result<Vec<SlotDiff>>> + Send {
+        async move {
+            backup.validate()?;
+            let diffs = diff_presets(&self.get_presets().await?, backup, options);
+            if options.is_dry_run() {
+                return Ok(diffs);
+            }
+            for diff in &diffs {
+                match (diff.action, &diff.wanted) {
+                    (SlotAction::Store | SlotAction::Relabel, Some(wanted)) => {
+                        self.store_preset(diff.slot, &wanted.content_item()).await?;
+                    }
+                    (SlotAction::Remove, _) => {
+                        self.remove_preset(diff.slot).await?;
+                    }
+                    _ => {}
+                }
+            }
+            Ok(diffs)
+        }
+    }
+
+    /// Gets the list of available sources
+    fn get_sources(&self) -> impl Future<Output = Result<Sources>> + Send;
+
+    /// Selects a source for playback
+    fn select_source(
+        &self,
+        source: &str,
+        source_account: Option<&str>,
+    ) -> impl Future<Output = Result<Status>> + Send;
+
+    /// Gets the current zone configuration
+    fn get_zone(&self) -> impl Future<Output = Result<Zone>> + Send;
+
+    /// Creates or updates a multi-room zone with this device as master
+    fn set_zone(
+        &self,
+        slave_devices: &[(String, String)],
+    ) -> impl Future<Output = Result<Status>> + Send;
+
+    /// Adds a slave device to the existing zone
+    fn add_zone_slave(
+        &self,
+        slave_ip: &str,
+        slave_mac: &str,
+    ) -> impl Future<Output = Result<Status>> + Send;
+
+    /// Removes a slave device from the zone
+    fn remove_zone_slave(
+        &self,
+        slave_ip: &str,
+        slave_mac: &str,
+    ) -> impl Future<Output = Result<Status>> + Send;
+
+    /// Gets the bass capabilities of the device
+    fn get_bass_capabilities(&self) -> impl Future<Output = Result<BassCapabilities>> + Send;
+
+    /// Gets the current bass settings
+    fn get_bass(&self) -> impl Future<Output = Result<Bass>> + Send;
+
+    /// Sets the bass level
+    fn set_bass(&self, value: i32) -> impl Future<Output = Result<Status>> + Send;
+
+    /// Simulates pressing and releasing a key on the remote
+    fn press_and_release_key(
+        &self,
+        key_value: &KeyValue,
+    ) -> impl Future<Output = Result<Status>> + Send;
+
+    /// Starts playback
+    fn play(&self) -> impl Future<Output = Result<Status>> + Send {
+        self.press_and_release_key(&KeyValue::Play)
+    }
+
+    /// Pauses playback
+    fn pause(&self) -> impl Future<Output = Result<Status>> + Send {
+        self.press_and_release_key(&KeyValue::Pause)
+    }
+
+    /// Stops playback
+    fn stop(&self) -> impl Future<Output = Result<Status>> + Send {
+        self.press_and_release_key(&KeyValue::Stop)
+    }
+
+    /// Skips to next track
+    fn next_track(&self) -> impl Future<Output = Result<Status>> + Send {
+        self.press_and_release_key(&KeyValue::NextTrack)
+    }
+
+    /// Returns to previous track
+    fn prev_track(&self) -> impl Future<Output = Result<Status>> + Send {
+        self.press_and_release_key(&KeyValue::PrevTrack)
+    }
+
+    /// Enables or disables shuffle
+    fn set_shuffle(&self, on: bool) -> impl Future<Output = Result<Status>> + Send {
+        async move {
+            self.press_and_release_key(&ShuffleMode::from(on).key())
+                .await
+        }
+    }
+
+    /// Sets the repeat mode
+    fn set_repeat(&self, mode: RepeatMode) -> impl Future<Output = Result<Status>> + Send {
+        async move { self.press_and_release_key(&mode.key()).await }
+    }
+
+    /// Toggles mute state
+    fn mute(&self) -> impl Future<Output = Result<Status>> + Send {
+        self.press_and_release_key(&KeyValue::Mute)
+    }
+
+    /// Mutes or unmutes the device
+    ///
+    /// Implementations check the mute state and change it atomically, so that
+    /// concurrent calls do not toggle it twice.
+    fn set_mute(&self, muted: bool) -> impl Future<Output = Result<()>> + Send;
+
+    /// Toggles power state
+    fn power(&self) -> impl Future<Output = Result<Status>> + Send {
+        self.press_and_release_key(&KeyValue::Power)
+    }
+}
+
+impl SoundTouchApi for BoseClient {
+    async fn get_info(&self) -> Result<DeviceInfo> {
+        BoseClient::get_info(self).await
+    }
+
+    async fn set_name(&self, name: &str) -> Result<Status> {
+        BoseClient::set_name(self, name).await
+    }
+
+    async fn get_status(&self) -> Result<NowPlaying> {
+        BoseClient::get_status(self).await
+    }
+
+    async fn get_track_info(&self) -> Result<NowPlaying> {
+        BoseClient::get_track_info(self).await
+    }
+
+    async fn get_volume(&self) -> Result<Volume> {
+        BoseClient::get_volume(self).await
+    }
+
+    async fn set_volume(&self, value: i32) -> Result<Status> {
+        BoseClient::set_volume(self, value).await
+    }
+
+    async fn volume_up(&self, step: u8) -> Result<VolumeLevel> {
+        BoseClient::volume_up(self, step).await
+    }
+
+    async fn volume_down(&self, step: u8) -> Result<VolumeLevel> {
+        BoseClient::volume_down(self, step).await
+    }
+
+    async fn get_presets(&self) -> Result<Presets> {
+        BoseClient::get_presets(self).await
+    }
+
+    async fn get_recents(&self) -> Result<Recents> {
+        BoseClient::get_recents(self).await
+    }
+
+    async fn set_preset(&self, value: i32) -> Result<Status> {
+        BoseClient::set_preset(self, value).await
+    }
+
+    async fn store_preset(&self, id: i32, content_item: &ContentItem) -> Result<Presets> {
+        BoseClient::store_preset(self, id, content_item).await
+    }
+
+    async fn remove_preset(&self, id: i32) -> Result<Presets> {
+        BoseClient::remove_preset(self, id).await
+    }
+
+    async fn get_sources(&self) -> Result<Sources> {
+        BoseClient::get_sources(self).await
+    }
+
+    async fn select_source(&self, source: &str, source_account: Option<&str>) -> Result<Status> {
+        BoseClient::select_source(self, source, source_account).await
+    }
+
+    async fn get_zone(&self) -> Result<Zone> {
+        BoseClient::get_zone(self).await
+    }
+
+    async fn set_zone(&self, slave_devices: &[(String, String)]) -> Result<Status> {
+        BoseClient::set_zone(self, slave_devices).await
+    }
+
+    async fn add_zone_slave(&self, slave_ip: &str, slave_mac: &str) -> Result<Status> {
+        BoseClient::add_zone_slave(self, slave_ip, slave_mac).await
+    }
+
+    async fn remove_zone_slave(&self, slave_ip: &str, slave_mac: &str) -> Result<Status> {
+        BoseClient::remove_zone_slave(self, slave_ip, slave_mac).await
+    }
+
+    async fn get_bass_capabilities(&self) -> Result<BassCapabilities> {
+        BoseClient::get_bass_capabilities(self).await
+    }
+
+    async fn get_bass(&self) -> Result<Bass> {
+        BoseClient::get_bass(self).await
+    }
+
+    async fn set_bass(&self, value: i32) -> Result<Status> {
+        BoseClient::set_bass(self, value).await
+    }
+
+    async fn press_and_release_key(&self, key_value: &KeyValue) -> Result<Status> {
+        BoseClient::press_and_release_key(self, key_value).await
+    }
+
+    async fn set_mute(&self, muted: bool) -> Result<()> {
+        BoseClient::set_mute(self, muted).await
+    }
+}
@@ -0,0 +1,296 @@
+//! Portable backups of the presets of a device
+
+use crate::{BoseError, ContentItem, DeviceInfo, Preset, Presets, Result, Source};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Version of the [`PresetBackup`] format written by this crate
+pub const PRESET_BACKUP_VERSION: u32 = 1;
+
+/// Presets of a device in a portable, versioned format
+///
+/// Unlike [`Presets`], which mirrors the XML of the device, the backup uses
+/// plain field names, so it can be stored as JSON, TOML or any other serde
+/// format and applied to another device with
+/// [`import_presets`](crate::BoseClient::import_presets).
+///
+/// # Example
+/// ```no_run
+/// # use bose_soundtouch::{BoseClient, ImportOptions, SlotPolicy};
+/// # async fn example() -> bose_soundtouch::Result<()> {
+/// let living_room = BoseClient::new_from_string("192.168.1.143");
+/// let kitchen = BoseClient::new_from_string("192.168.1.144");
+///
+/// let backup = living_room.export_presets().await?;
+/// let options = ImportOptions::new().policy(SlotPolicy::Skip).dry_run(true);
+/// for slot in kitchen.import_presets(&backup, &options).await? {
+///     println!("{}: {:?}", slot.slot, slot.action);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PresetBackup {
+    /// Version of the format, see [`PRESET_BACKUP_VERSION`]
+    pub version: u32,
+    /// Id of the device the presets were exported from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device_id: Option<String>,
+    /// Name of the device the presets were exported from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device_name: Option<String>,
+    /// Stored presets, ordered by slot
+    #[serde(default)]
+    pub presets: Vec<PresetEntry>,
+}
+
+/// A single preset slot in a [`PresetBackup`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PresetEntry {
+    /// Preset number (1-6)
+    pub slot: i32,
+    /// Source type (e.g., TUNEIN, SPOTIFY)
+    pub source: Source,
+    /// Content type (e.g., stationurl, tracklisturl)
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    /// Content location/URL
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    /// Account associated with the source
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_account: Option<String>,
+    /// Display name of the content
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// URL of the artwork
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub art: Option<String>,
+    /// Whether the content can be saved as a preset
+    #[serde(default = "presetable")]
+    pub is_presetable: bool,
+}
+
+fn presetable() -> bool {
+    true
+}
+
+impl From<&Preset> for PresetEntry {
+    fn from(preset: &Preset) -> Self {
+        let item = &preset.content_item;
+        Self {
+            slot: preset.id,
+            source: item.source.clone(),
+            content_type: item.content_type.clone(),
+            location: item.location.clone(),
+            source_account: item.source_account.clone(),
+            name: item.name.clone(),
+            art: item.container_art.clone(),
+            is_presetable: item.is_presetable,
+        }
+    }
+}
+
+impl PresetEntry {
+    /// Content item to store in the slot
+    pub fn content_item(&self) -> ContentItem {
+        ContentItem {
+            source: self.source.clone(),
+            content_type: self.content_type.clone(),
+            location: self.location.clone(),
+            source_account: self.source_account.clone(),
+            is_presetable: self.is_presetable,
+            name: self.name.clone(),
+            container_art: self.art.clone(),
+        }
+    }
+
+    /// Whether both entries point to the same content
+    ///
+    /// Only the source, type, location and account are compared, so entries
+    /// for the same station under another name or artwork are the same content.
+    pub fn same_content(&self, other: &PresetEntry) -> bool {
+        self.source == other.source
+            && self.content_type == other.content_type
+            && self.location == other.location
+            && self.source_account == other.source_account
+    }
+}
+
+impl PresetBackup {
+    /// Creates a backup of the given presets
+    pub fn new(presets: &Presets) -> Self {
+        let mut entries: Vec<PresetEntry> = presets.items.iter().map(PresetEntry::from).collect();
+        entries.sort_by_key(|entry| entry.slot);
+        Self {
+            version: PRESET_BACKUP_VERSION,
+            device_id: None,
+            device_name: None,
+            presets: entries,
+        }
+    }
+
+    /// Records the device the presets were exported from
+    pub fn with_device(mut self, info: &DeviceInfo) -> Self {
+        self.device_id = Some(info.device_id.clone());
+        self.device_name = Some(info.name.clone());
+        self
+    }
+
+    /// Entry for a slot, if the slot is stored in the backup
+    pub fn entry(&self, slot: i32) -> Option<&PresetEntry> {
+        self.presets.iter().find(|entry| entry.slot == slot)
+    }
+
+    /// Checks that the backup can be applied by this version of the crate
+    ///
+    /// # Errors
+    /// Returns `BoseError::UnsupportedBackupVersion` for backups written by a
+    /// newer format version and `BoseError::InvalidPreset` for slots outside 1-6
+    /// or slots that appear more than once.
+    pub fn validate(&self) -> Result<()> {
+        if self.version == 0 || self.version > PRESET_BACKUP_VERSION {
+            return Err(BoseError::UnsupportedBackupVersion(self.version));
+        }
+        let mut seen = Vec::new();
+        for entry in &self.presets {
+            crate::validate_preset_id(entry.slot)?;
+            if seen.contains(&entry.slot) {
+                return Err(BoseError::InvalidPreset(format!(
+                    "{} appears more than once.",
+                    entry.slot
+                )));
+            }
+            seen.push(entry.slot);
+        }
+        Ok(())
+    }
+
+    /// Serializes the backup to pretty-printed JSON
+    #[cfg(feature = "json")]
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Parses and validates a backup from JSON
+    #[cfg(feature = "json")]
+    pub fn from_json(json: &str) -> Result<Self> {
+        let backup: Self = serde_json::from_str(json)?;
+        backup.validate()?;
+        Ok(backup)
+    }
+}
+
+/// What to do with a slot that already holds a preset when importing
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SlotPolicy {
+    /// Make the slot match the backup, clearing it if the backup has no preset there
+    #[default]
+    Overwrite,
+    /// Leave the slot untouched; only empty slots are filled
+    Skip,
+}
+
+/// Options for [`import_presets`](crate::BoseClient::import_presets)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ImportOptions {
+    policy: SlotPolicy,
+    slots: HashMap<i32, SlotPolicy>,
+    dry_run: bool,
+}
+
+impl ImportOptions {
+    /// Creates options that overwrite every slot
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the policy for slots without a policy of their own (default: overwrite)
+    pub fn policy(mut self, policy: SlotPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Sets the policy for a single slot
+    pub fn slot_policy(mut self, slot: i32, policy: SlotPolicy) -> Self {
+        self.slots.insert(slot, policy);
+        self
+    }
+
+    /// Only computes the changes without applying them (default: false)
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Policy that applies to a slot
+    pub fn policy_for(&self, slot: i32) -> SlotPolicy {
+        self.slots.get(&slot).copied().unwrap_or(self.policy)
+    }
+
+    /// Whether changes are only computed
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+}
+
+/// Change to a preset slot when importing a backup
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SlotAction {
+    /// The slot already matches the backup
+    Unchanged,
+    /// The preset of the backup is stored in the slot
+    Store,
+    /// The slot holds the same content under another name or artwork, so the
+    /// preset of the backup is stored again to update them
+    Relabel,
+    /// The slot is cleared because the backup has no preset there
+    Remove,
+    /// The slot differs but is left untouched because of its [`SlotPolicy`]
+    Skip,
+}
+
+/// Difference between a preset slot of a device and a backup
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlotDiff {
+    /// Preset number (1-6)
+    pub slot: i32,
+    /// Preset currently stored on the device
+    pub current: Option<PresetEntry>,
+    /// Preset stored in the backup
+    pub wanted: Option<PresetEntry>,
+    /// What happens to the slot
+    pub action: SlotAction,
+}
+
+/// Compares the presets of a device with a backup, slot by slot
+///
+/// Returns one [`SlotDiff`] for every slot from 1 to 6.
+pub fn diff_presets(
+    current: &Presets,
+    backup: &PresetBackup,
+    options: &ImportOptions,
+) -> Vec<SlotDiff> {
+    let current = PresetBackup::new(current);
+    (1..=6)
+        .map(|slot| {
+            let have = current.entry(slot).cloned();
+            let wanted = backup.entry(slot).cloned();
+            let action = match (&have, &wanted) {
+                (have, wanted) if have == wanted => SlotAction::Unchanged,
+                (Some(_), _) if options.policy_for(slot) == SlotPolicy::Skip => SlotAction::Skip,
+                (Some(have), Some(wanted)) if have.same_content(wanted) => SlotAction::Relabel,
+                (_, Some(_)) => SlotAction::Store,
+                (_, None) => SlotAction::Remove,
+            };
+            SlotDiff {
+                slot,
+                current: have,
+                wanted,
+                action,
+            }
+        })
+        .collect()
+}
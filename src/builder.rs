@@ -0,0 +1,186 @@
+//! Builder for configuring a [`BoseClient`]
+
+use crate::{BoseClient, Result, RetryPolicy, VolumeLevel, DEFAULT_PORT, DEFAULT_WEBSOCKET_PORT};
+#[cfg(feature = "websocket")]
+use crate::{DEFAULT_PING_INTERVAL, DEFAULT_PONG_TIMEOUT};
+use reqwest::Client;
+use std::time::Duration;
+
+/// Builder for a [`BoseClient`]
+///
+/// All requests made by the resulting client share a single [`reqwest::Client`],
+/// so connections to the device are reused.
+///
+/// # Example
+/// ```no_run
+/// # use bose_soundtouch::BoseClient;
+/// # use std::time::Duration;
+/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = BoseClient::builder("192.168.1.143")
+///     .connect_timeout(Duration::from_secs(2))
+///     .timeout(Duration::from_secs(5))
+///     .user_agent("my-controller/1.0")
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct BoseClientBuilder {
+    hostname: String,
+    port: u16,
+    websocket_port: u16,
+    connect_timeout: Option<Duration>,
+    timeout: Option<Duration>,
+    user_agent: Option<String>,
+    http_client: Option<Client>,
+    retry_policy: RetryPolicy,
+    max_volume: Option<VolumeLevel>,
+    #[cfg(feature = "websocket")]
+    enrich_events: bool,
+    #[cfg(feature = "websocket")]
+    ping_interval: Option<Duration>,
+    #[cfg(feature = "websocket")]
+    pong_timeout: Duration,
+}
+
+impl BoseClientBuilder {
+    /// Creates a new builder for the given device
+    ///
+    /// # Arguments
+    /// * `hostname` - IP address or hostname of the SoundTouch device
+    pub fn new<S: Into<String>>(hostname: S) -> Self {
+        Self {
+            hostname: hostname.into(),
+            port: DEFAULT_PORT,
+            websocket_port: DEFAULT_WEBSOCKET_PORT,
+            connect_timeout: None,
+            timeout: None,
+            user_agent: None,
+            http_client: None,
+            retry_policy: RetryPolicy::default(),
+            max_volume: None,
+            #[cfg(feature = "websocket")]
+            enrich_events: false,
+            #[cfg(feature = "websocket")]
+            ping_interval: Some(DEFAULT_PING_INTERVAL),
+            #[cfg(feature = "websocket")]
+            pong_timeout: DEFAULT_PONG_TIMEOUT,
+        }
+    }
+
+    /// Sets the port of the HTTP API (default: 8090)
+    pub fn port(mut self, port: u16) -> Self {
+        self.port = port;
+        self
+    }
+
+    /// Sets the port of the WebSocket API (default: 8080)
+    pub fn websocket_port(mut self, port: u16) -> Self {
+        self.websocket_port = port;
+        self
+    }
+
+    /// Sets the timeout for establishing a connection to the device
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the total timeout of a single request, from connecting until the body is read
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the `User-Agent` header sent with every request
+    pub fn user_agent<S: Into<String>>(mut self, user_agent: S) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Uses an existing HTTP client for all requests
+    ///
+    /// When set, the timeouts and user agent configured on this builder are
+    /// ignored; configure them on the provided client instead.
+    pub fn http_client(mut self, client: Client) -> Self {
+        self.http_client = Some(client);
+        self
+    }
+
+    /// Sets the policy used to retry failed requests
+    ///
+    /// See [`RetryPolicy`] for the defaults.
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// Limits the volume levels the client will set
+    ///
+    /// See [`BoseClient::set_max_volume`].
+    pub fn max_volume(mut self, max: VolumeLevel) -> Self {
+        self.max_volume = Some(max);
+        self
+    }
+
+    /// Enriches bare change notifications with the current state of the device
+    ///
+    /// See [`BoseClient::set_enrich_events`].
+    #[cfg(feature = "websocket")]
+    pub fn enrich_events(mut self, enabled: bool) -> Self {
+        self.enrich_events = enabled;
+        self
+    }
+
+    /// Sets the interval between keepalive pings on the WebSocket (default: 30s)
+    ///
+    /// `None` disables the pings. See [`BoseClient::set_ping_interval`].
+    #[cfg(feature = "websocket")]
+    pub fn ping_interval(mut self, interval: Option<Duration>) -> Self {
+        self.ping_interval = interval;
+        self
+    }
+
+    /// Sets the time to wait for the answer to a keepalive ping (default: 10s)
+    #[cfg(feature = "websocket")]
+    pub fn pong_timeout(mut self, timeout: Duration) -> Self {
+        self.pong_timeout = timeout;
+        self
+    }
+
+    /// Builds the client
+    ///
+    /// # Errors
+    /// Returns `BoseError::HttpClientError` if the HTTP client could not be created
+    pub fn build(self) -> Result<BoseClient> {
+        let http = match self.http_client {
+            Some(client) => client,
+            None => {
+                let mut builder = Client::builder();
+                if let Some(timeout) = self.connect_timeout {
+                    builder = builder.connect_timeout(timeout);
+                }
+                if let Some(timeout) = self.timeout {
+                    builder = builder.timeout(timeout);
+                }
+                if let Some(user_agent) = self.user_agent {
+                    builder = builder.user_agent(user_agent);
+                }
+                builder.build()?
+            }
+        };
+
+        let mut client = BoseClient::with_http_client(self.hostname, http);
+        client.port = self.port;
+        client.websocket_port = self.websocket_port;
+        client.retry_policy = self.retry_policy;
+        client.max_volume = self.max_volume;
+        #[cfg(feature = "websocket")]
+        {
+            client.enrich_events = self.enrich_events;
+            client.ping_interval = self.ping_interval;
+            client.pong_timeout = self.pong_timeout;
+        }
+        Ok(client)
+    }
+}
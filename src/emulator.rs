@@ -0,0 +1,787 @@
+//! Local emulator of a SoundTouch device for integration tests
+
+use crate::session::parse_msg;
+use crate::{
+    root_element, serialize_xml, Bass, BassCapabilities, BoseClient, BoseError, Call, DeviceErrors,
+    DeviceInfo, FakeSoundTouch, FakeState, KeyValue, NowPlaying, Preset, PresetSlot, Presets,
+    Result, SoundTouchApi, Sources, Volume, Zone,
+};
+use futures_util::{SinkExt, StreamExt};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream, ToSocketAddrs};
+use tokio::sync::{broadcast, watch};
+use tokio_tungstenite::tungstenite::handshake::server::{Request, Response};
+use tokio_tungstenite::tungstenite::protocol::Message;
+
+#[cfg(feature = "logging")]
+use log::{debug, error};
+
+/// Greeting sent by the device to every new WebSocket client
+const SDK_INFO: &str =
+    r#"<SoundTouchSdkInfo serverVersion="4" serverBuild="trunk r42017 v4 epdbuild hepdswbld04" />"#;
+
+/// Local server emulating the HTTP and WebSocket APIs of a SoundTouch device
+///
+/// The emulator keeps its state in a [`FakeSoundTouch`], serves the documented
+/// HTTP API and pushes `<updates>` notifications to WebSocket clients that
+/// negotiate the `gabbo` protocol whenever that state changes. Requests sent as
+/// gabbo `<msg>` envelopes over the WebSocket are answered like their HTTP
+/// counterparts. Both servers stop when the emulator is dropped.
+///
+/// # Example
+/// ```
+/// use bose_soundtouch::Emulator;
+/// use bose_soundtouch::FakeState;
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> bose_soundtouch::Result<()> {
+/// let emulator = Emulator::start(FakeState::default()).await?;
+/// let client = emulator.client()?;
+///
+/// client.set_volume(42).await?;
+/// assert_eq!(client.get_volume().await?.actual, 42);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Emulator {
+    shared: Arc<Shared>,
+    http_addr: SocketAddr,
+    websocket_addr: SocketAddr,
+    // Dropping the sender stops all server tasks
+    _shutdown: watch::Sender<()>,
+}
+
+#[derive(Debug)]
+struct Shared {
+    device: FakeSoundTouch,
+    updates: broadcast::Sender<String>,
+    // Sending a value closes all WebSocket connections
+    disconnect: watch::Sender<()>,
+    // Sending a value makes all WebSocket connections stop responding
+    freeze: watch::Sender<()>,
+}
+
+impl Emulator {
+    /// Starts an emulator on ephemeral ports of the loopback interface
+    ///
+    /// Use [`client`](Self::client) to get a client connected to it.
+    pub async fn start(state: FakeState) -> Result<Self> {
+        Self::bind(("127.0.0.1", 0), ("127.0.0.1", 0), state).await
+    }
+
+    /// Starts an emulator listening on the given addresses
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use bose_soundtouch::Emulator;
+    /// # use bose_soundtouch::{FakeState, DEFAULT_PORT, DEFAULT_WEBSOCKET_PORT};
+    /// # async fn example() -> bose_soundtouch::Result<()> {
+    /// let emulator = Emulator::bind(
+    ///     ("0.0.0.0", DEFAULT_PORT),
+    ///     ("0.0.0.0", DEFAULT_WEBSOCKET_PORT),
+    ///     FakeState::default(),
+    /// )
+    /// .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn bind<A, B>(http_addr: A, websocket_addr: B, state: FakeState) -> Result<Self>
+    where
+        A: ToSocketAddrs,
+        B: ToSocketAddrs,
+    {
+        let http_listener = TcpListener::bind(http_addr).await?;
+        let websocket_listener = TcpListener::bind(websocket_addr).await?;
+        let (shutdown, shutdown_rx) = watch::channel(());
+        let (updates, _) = broadcast::channel(100);
+
+        let emulator = Self {
+            shared: Arc::new(Shared {
+                device: FakeSoundTouch::with_state(state),
+                updates,
+                disconnect: watch::channel(()).0,
+                freeze: watch::channel(()).0,
+            }),
+            http_addr: http_listener.local_addr()?,
+            websocket_addr: websocket_listener.local_addr()?,
+            _shutdown: shutdown,
+        };
+
+        tokio::spawn(accept_http(
+            http_listener,
+            emulator.shared.clone(),
+            shutdown_rx.clone(),
+        ));
+        tokio::spawn(accept_websocket(
+            websocket_listener,
+            emulator.shared.clone(),
+            shutdown_rx,
+        ));
+        Ok(emulator)
+    }
+
+    /// Address of the HTTP API
+    pub fn http_addr(&self) -> SocketAddr {
+        self.http_addr
+    }
+
+    /// Address of the WebSocket API
+    pub fn websocket_addr(&self) -> SocketAddr {
+        self.websocket_addr
+    }
+
+    /// Creates a client that talks to this emulator
+    pub fn client(&self) -> Result<BoseClient> {
+        BoseClient::builder(self.http_addr.ip().to_string())
+            .port(self.http_addr.port())
+            .websocket_port(self.websocket_addr.port())
+            .build()
+    }
+
+    /// Returns a snapshot of the current state
+    pub fn state(&self) -> FakeState {
+        self.shared.device.state()
+    }
+
+    /// Modifies the current state and notifies WebSocket clients of the changes
+    pub fn update_state<F: FnOnce(&mut FakeState)>(&self, f: F) {
+        let before = self.state();
+        self.shared.device.update_state(f);
+        self.shared.notify(&before, &self.state());
+    }
+
+    /// Returns all requests handled so far, in order
+    ///
+    /// A key press and release is recorded as a single [`Call::Key`].
+    pub fn calls(&self) -> Vec<Call> {
+        self.shared.device.calls()
+    }
+
+    /// Makes the next request fail with the given error
+    ///
+    /// A [`BoseError::DeviceError`] is answered with an `<errors>` body, any other
+    /// error with HTTP status 500.
+    pub fn fail_next(&self, error: BoseError) {
+        self.shared.device.fail_next(error)
+    }
+
+    /// Number of connected WebSocket clients
+    pub fn websocket_clients(&self) -> usize {
+        self.shared.updates.receiver_count()
+    }
+
+    /// Closes all WebSocket connections, as a reboot of the device would
+    ///
+    /// New connections are accepted right away.
+    pub fn disconnect_websockets(&self) {
+        self.shared.disconnect.send_replace(());
+    }
+
+    /// Makes all open WebSocket connections stop responding without closing them
+    ///
+    /// This emulates a device that lost power and left a half-open connection
+    /// behind: no updates are sent and pings are no longer answered.
+    pub fn freeze_websockets(&self) {
+        self.shared.freeze.send_replace(());
+    }
+}
+
+impl Shared {
+    async fn handle(&self, method: &str, path: &str, body: &str) -> HttpResponse {
+        let before = self.device.state();
+        let result = self.route(method, path, body).await;
+        self.notify(&before, &self.device.state());
+
+        match result {
+            Ok(Some(body)) => HttpResponse::new(200, body),
+            Ok(None) => HttpResponse::new(404, String::new()),
+            Err(BoseError::XmlError(e)) => HttpResponse::new(
+                200,
+                errors_xml(
+                    &before.info.device_id,
+                    1019,
+                    "CLIENT_XML_ERROR",
+                    &e.to_string(),
+                ),
+            ),
+            Err(BoseError::DeviceError(errors)) => {
+                HttpResponse::new(200, device_errors_xml(&errors))
+            }
+            Err(e) => HttpResponse::new(
+                500,
+                errors_xml(&before.info.device_id, 1000, "UNKNOWN", &e.to_string()),
+            ),
+        }
+    }
+
+    /// Handles a request, or returns `None` for an unknown path
+    async fn route(&self, method: &str, path: &str, body: &str) -> Result<Option<String>> {
+        let device = &self.device;
+        let id = device.state().info.device_id;
+        let response = match (method, path) {
+            ("GET", "/info") => info_xml(&device.get_info().await?),
+            ("GET", "/now_playing") => now_playing_xml(&device.get_status().await?),
+            ("GET", "/trackInfo") => now_playing_xml(&device.get_track_info().await?),
+            ("GET", "/volume") => volume_xml(&device.get_volume().await?),
+            ("GET", "/presets") => presets_xml(&device.get_presets().await?),
+            ("GET", "/recents") => to_xml(&device.get_recents().await?),
+            ("GET", "/sources") => sources_xml(&id, &device.get_sources().await?),
+            ("GET", "/getZone") => zone_xml(&device.get_zone().await?),
+            ("GET", "/bass") => bass_xml(&id, &device.get_bass().await?),
+            ("GET", "/bassCapabilities") => {
+                bass_capabilities_xml(&id, &device.get_bass_capabilities().await?)
+            }
+            ("POST", "/key") => {
+                // The client sends a press and a release; act on the press only
+                let key: KeyPress = parse_body(body, "key")?;
+                if key.state == "press" {
+                    device.press_and_release_key(&key.value).await?;
+                }
+                status_xml(path)
+            }
+            ("POST", "/volume") => {
+                let volume: Text<i32> = parse_body(body, "volume")?;
+                device.set_volume(volume.value).await?;
+                status_xml(path)
+            }
+            ("POST", "/bass") => {
+                let bass: Text<i32> = parse_body(body, "bass")?;
+                device.set_bass(bass.value).await?;
+                status_xml(path)
+            }
+            ("POST", "/name") => {
+                let name: Text<String> = parse_body(body, "name")?;
+                device.set_name(&name.value).await?;
+                status_xml(path)
+            }
+            ("POST", "/select") => {
+                let item: SelectRequest = parse_body(body, "ContentItem")?;
+                // The client sends an empty account when there is none
+                let source_account = item.source_account.filter(|a| !a.is_empty());
+                device
+                    .select_source(&item.source, source_account.as_deref())
+                    .await?;
+                status_xml(path)
+            }
+            ("POST", "/storePreset") => {
+                let preset: Preset = parse_body(body, "preset")?;
+                presets_xml(&device.store_preset(preset.id, &preset.content_item).await?)
+            }
+            ("POST", "/removePreset") => {
+                let preset: PresetSlot = parse_body(body, "preset")?;
+                presets_xml(&device.remove_preset(preset.id).await?)
+            }
+            ("POST", "/setZone") => {
+                let zone: Zone = parse_body(body, "zone")?;
+                let slaves: Vec<(String, String)> = zone
+                    .members
+                    .into_iter()
+                    .filter(|m| m.mac_address != zone.master)
+                    .map(|m| (m.ip_address, m.mac_address))
+                    .collect();
+                device.set_zone(&slaves).await?;
+                status_xml(path)
+            }
+            ("POST", "/addZoneSlave") => {
+                let zone: Zone = parse_body(body, "zone")?;
+                for member in &zone.members {
+                    device
+                        .add_zone_slave(&member.ip_address, &member.mac_address)
+                        .await?;
+                }
+                status_xml(path)
+            }
+            ("POST", "/removeZoneSlave") => {
+                let zone: Zone = parse_body(body, "zone")?;
+                for member in &zone.members {
+                    device
+                        .remove_zone_slave(&member.ip_address, &member.mac_address)
+                        .await?;
+                }
+                status_xml(path)
+            }
+            _ => return Ok(None),
+        };
+        Ok(Some(response))
+    }
+
+    /// Sends an `<updates>` message for every part of the state that changed
+    fn notify(&self, before: &FakeState, after: &FakeState) {
+        let id = &after.info.device_id;
+        let mut changes = Vec::new();
+
+        let volume = volume_xml(&after.volume);
+        if volume_xml(&before.volume) != volume {
+            changes.push(format!("<volumeUpdated>{}</volumeUpdated>", volume));
+        }
+        let now_playing = now_playing_xml(&after.now_playing);
+        if now_playing_xml(&before.now_playing) != now_playing {
+            changes.push(format!(
+                "<nowPlayingUpdated>{}</nowPlayingUpdated>",
+                now_playing
+            ));
+        }
+        let presets = presets_xml(&after.presets);
+        if presets_xml(&before.presets) != presets {
+            changes.push(format!("<presetsUpdated>{}</presetsUpdated>", presets));
+        }
+        let zone = zone_xml(&after.zone);
+        if zone_xml(&before.zone) != zone {
+            changes.push(format!("<zoneUpdated>{}</zoneUpdated>", zone));
+        }
+        if bass_xml(id, &before.bass) != bass_xml(id, &after.bass) {
+            changes.push("<bassUpdated />".to_string());
+        }
+        if sources_xml(id, &before.sources) != sources_xml(id, &after.sources) {
+            changes.push("<sourcesUpdated />".to_string());
+        }
+        if info_xml(&before.info) != info_xml(&after.info) {
+            changes.push("<infoUpdated />".to_string());
+        }
+
+        for change in changes {
+            let message = format!(r#"<updates deviceID="{}">{}</updates>"#, escape(id), change);
+            #[cfg(feature = "logging")]
+            debug!("Emulator notification: {}", message);
+            // Sending only fails when no client is connected
+            let _ = self.updates.send(message);
+        }
+    }
+}
+
+async fn accept_http(
+    listener: TcpListener,
+    shared: Arc<Shared>,
+    mut shutdown: watch::Receiver<()>,
+) {
+    loop {
+        tokio::select! {
+            _ = shutdown.changed() => return,
+            accepted = listener.accept() => match accepted {
+                Ok((stream, _)) => {
+                    tokio::spawn(serve_http(stream, shared.clone(), shutdown.clone()));
+                }
+                Err(_e) => {
+                    #[cfg(feature = "logging")]
+                    error!("Emulator failed to accept HTTP connection: {}", _e);
+                }
+            },
+        }
+    }
+}
+
+async fn serve_http(stream: TcpStream, shared: Arc<Shared>, mut shutdown: watch::Receiver<()>) {
+    let (read, mut write) = stream.into_split();
+    let mut reader = BufReader::new(read);
+    loop {
+        let request = tokio::select! {
+            _ = shutdown.changed() => return,
+            request = read_request(&mut reader) => request,
+        };
+        let Ok(Some(request)) = request else {
+            return;
+        };
+        #[cfg(feature = "logging")]
+        debug!(
+            "Emulator request: {} {} {}",
+            request.method, request.path, request.body
+        );
+        let response = shared
+            .handle(&request.method, &request.path, &request.body)
+            .await;
+        if write.write_all(&response.to_bytes()).await.is_err() {
+            return;
+        }
+    }
+}
+
+struct HttpRequest {
+    method: String,
+    path: String,
+    body: String,
+}
+
+/// Reads the next request of a connection, or `None` when the client closed it
+async fn read_request<R>(reader: &mut R) -> std::io::Result<Option<HttpRequest>>
+where
+    R: AsyncBufReadExt + Unpin,
+{
+    let mut line = String::new();
+    if reader.read_line(&mut line).await? == 0 {
+        return Ok(None);
+    }
+    let mut parts = line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let target = parts.next().unwrap_or_default();
+    let path = target.split('?').next().unwrap_or_default().to_string();
+
+    let mut content_length = 0;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line).await? == 0 {
+            return Ok(None);
+        }
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            }
+        }
+    }
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).await?;
+    Ok(Some(HttpRequest {
+        method,
+        path,
+        body: String::from_utf8_lossy(&body).into_owned(),
+    }))
+}
+
+struct HttpResponse {
+    status: u16,
+    body: String,
+}
+
+impl HttpResponse {
+    fn new(status: u16, body: String) -> Self {
+        Self { status, body }
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let reason = match self.status {
+            200 => "OK",
+            404 => "Not Found",
+            _ => "Internal Server Error",
+        };
+        format!(
+            "HTTP/1.1 {} {}\r\nContent-Type: text/xml; charset=utf-8\r\nContent-Length: {}\r\n\r\n{}",
+            self.status,
+            reason,
+            self.body.len(),
+            self.body
+        )
+        .into_bytes()
+    }
+}
+
+async fn accept_websocket(
+    listener: TcpListener,
+    shared: Arc<Shared>,
+    mut shutdown: watch::Receiver<()>,
+) {
+    loop {
+        tokio::select! {
+            _ = shutdown.changed() => return,
+            accepted = listener.accept() => match accepted {
+                Ok((stream, _)) => {
+                    tokio::spawn(serve_websocket(stream, shared.clone(), shutdown.clone()));
+                }
+                Err(_e) => {
+                    #[cfg(feature = "logging")]
+                    error!("Emulator failed to accept WebSocket connection: {}", _e);
+                }
+            },
+        }
+    }
+}
+
+async fn serve_websocket(
+    stream: TcpStream,
+    shared: Arc<Shared>,
+    mut shutdown: watch::Receiver<()>,
+) {
+    // Subscribe before the handshake so no update is missed once the client is connected
+    let mut updates = shared.updates.subscribe();
+    let mut disconnect = shared.disconnect.subscribe();
+    let mut freeze = shared.freeze.subscribe();
+    #[allow(clippy::result_large_err)] // Signature required by tungstenite
+    let accept_gabbo = |request: &Request, mut response: Response| {
+        let gabbo = request
+            .headers()
+            .get("Sec-WebSocket-Protocol")
+            .and_then(|h| h.to_str().ok())
+            .is_some_and(|protocols| protocols.split(',').any(|p| p.trim() == "gabbo"));
+        if gabbo {
+            response
+                .headers_mut()
+                .insert("Sec-WebSocket-Protocol", "gabbo".parse().unwrap());
+        }
+        Ok(response)
+    };
+    let Ok(mut ws) = tokio_tungstenite::accept_hdr_async(stream, accept_gabbo).await else {
+        return;
+    };
+    if ws.send(Message::Text(SDK_INFO.into())).await.is_err() {
+        return;
+    }
+
+    loop {
+        tokio::select! {
+            _ = shutdown.changed() => {
+                let _ = ws.close(None).await;
+                return;
+            }
+            _ = disconnect.changed() => {
+                let _ = ws.close(None).await;
+                return;
+            }
+            _ = freeze.changed() => {
+                // Keep the connection open without reading or writing
+                let _ = shutdown.changed().await;
+                return;
+            }
+            update = updates.recv() => match update {
+                Ok(xml) => {
+                    if ws.send(Message::Text(xml.into())).await.is_err() {
+                        return;
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => return,
+            },
+            message = ws.next() => match message {
+                Some(Ok(Message::Text(text))) => {
+                    // Answer gabbo requests like the HTTP API, echoing the header
+                    let Ok((header, body)) = parse_msg(&text) else {
+                        continue;
+                    };
+                    let path = format!("/{}", header.url);
+                    let response = shared
+                        .handle(&header.method, &path, body.unwrap_or_default())
+                        .await;
+                    let reply = header.to_msg(Some(&response.body));
+                    if ws.send(Message::Text(reply.into())).await.is_err() {
+                        return;
+                    }
+                }
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return,
+                Some(Ok(_)) => continue,
+            },
+        }
+    }
+}
+
+/// Body of a `/key` request
+#[derive(Deserialize)]
+struct KeyPress {
+    #[serde(rename = "@state")]
+    state: String,
+    #[serde(rename = "$text")]
+    value: KeyValue,
+}
+
+/// Body of a `/select` request
+#[derive(Deserialize)]
+struct SelectRequest {
+    #[serde(rename = "@source")]
+    source: String,
+    #[serde(rename = "@sourceAccount")]
+    source_account: Option<String>,
+}
+
+/// Body consisting of a single element with text content, such as `<volume>42</volume>`
+#[derive(Deserialize)]
+struct Text<T> {
+    #[serde(rename = "$text")]
+    value: T,
+}
+
+/// Deserializes a request body, checking that its root element is `root`
+fn parse_body<T: DeserializeOwned>(body: &str, root: &str) -> Result<T> {
+    if root_element(body) != Some(root) {
+        return Err(BoseError::XmlError(serde::de::Error::custom(format!(
+            "expected <{}> element",
+            root
+        ))));
+    }
+    quick_xml::de::from_str(body).map_err(BoseError::XmlError)
+}
+
+fn escape(value: &str) -> String {
+    quick_xml::escape::escape(value).into_owned()
+}
+
+/// Returns `<name>value</name>`, or nothing when there is no value
+fn optional_element(name: &str, value: Option<&str>) -> String {
+    value
+        .map(|v| format!("<{0}>{1}</{0}>", name, escape(v)))
+        .unwrap_or_default()
+}
+
+/// Returns ` name="value"`, or nothing when there is no value
+fn optional_attribute(name: &str, value: Option<&str>) -> String {
+    value
+        .map(|v| format!(r#" {}="{}""#, name, escape(v)))
+        .unwrap_or_default()
+}
+
+fn status_xml(path: &str) -> String {
+    format!("<status>{}</status>", escape(path))
+}
+
+fn errors_xml(device_id: &str, value: i32, name: &str, message: &str) -> String {
+    format!(
+        r#"<errors deviceID="{}"><error value="{}" name="{}" severity="Unknown">{}</error></errors>"#,
+        escape(device_id),
+        value,
+        escape(name),
+        escape(message)
+    )
+}
+
+fn device_errors_xml(errors: &DeviceErrors) -> String {
+    let mut xml = format!(
+        "<errors{}>",
+        optional_attribute("deviceID", errors.device_id.as_deref())
+    );
+    for error in &errors.errors {
+        let _ = write!(
+            xml,
+            r#"<error value="{}" name="{}" severity="{}">{}</error>"#,
+            error.value,
+            escape(&error.name),
+            escape(&error.severity),
+            escape(&error.message)
+        );
+    }
+    xml.push_str("</errors>");
+    xml
+}
+
+fn info_xml(info: &DeviceInfo) -> String {
+    let mut xml = format!(
+        r#"<info deviceID="{}"><name>{}</name><type>{}</type><margeAccountUUID>{}</margeAccountUUID><components>"#,
+        escape(&info.device_id),
+        escape(&info.name),
+        escape(&info.device_type),
+        escape(&info.marge_account_uuid)
+    );
+    for component in &info.components.component {
+        let _ = write!(
+            xml,
+            "<component><componentCategory>{}</componentCategory>{}{}</component>",
+            escape(&component.category),
+            optional_element("softwareVersion", component.software_version.as_deref()),
+            optional_element("serialNumber", component.serial_number.as_deref())
+        );
+    }
+    let _ = write!(
+        xml,
+        "</components><margeURL>{}</margeURL>",
+        escape(&info.marge_url)
+    );
+    for network in &info.network_info {
+        let _ = write!(
+            xml,
+            r#"<networkInfo type="{}"><macAddress>{}</macAddress><ipAddress>{}</ipAddress></networkInfo>"#,
+            escape(&network.network_type),
+            escape(&network.mac_address),
+            escape(&network.ip_address)
+        );
+    }
+    let _ = write!(
+        xml,
+        "<moduleType>{}</moduleType><variant>{}</variant><variantMode>{}</variantMode><countryCode>{}</countryCode><regionCode>{}</regionCode></info>",
+        escape(&info.module_type),
+        escape(&info.variant),
+        escape(&info.variant_mode),
+        escape(&info.country_code),
+        escape(&info.region_code)
+    );
+    xml
+}
+
+/// Serializes one of the crate's models, which mirror the device's XML
+fn to_xml<T: Serialize>(value: &T) -> String {
+    serialize_xml(value).unwrap_or_default()
+}
+
+fn now_playing_xml(now_playing: &NowPlaying) -> String {
+    to_xml(now_playing)
+}
+
+/// Converts a `CamelCase` variant name to the `SCREAMING_SNAKE_CASE` used by the device
+fn variant_name<T: std::fmt::Debug>(value: &T) -> String {
+    let mut name = String::new();
+    for (i, c) in format!("{:?}", value).chars().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            name.push('_');
+        }
+        name.push(c.to_ascii_uppercase());
+    }
+    name
+}
+
+fn volume_xml(volume: &Volume) -> String {
+    to_xml(volume)
+}
+
+fn presets_xml(presets: &Presets) -> String {
+    to_xml(presets)
+}
+
+fn sources_xml(device_id: &str, sources: &Sources) -> String {
+    let mut xml = format!(r#"<sources deviceID="{}">"#, escape(device_id));
+    for item in &sources.items {
+        let _ = write!(
+            xml,
+            r#"<sourceItem source="{}"{} status="{}">{}</sourceItem>"#,
+            escape(&item.source),
+            optional_attribute("sourceAccount", item.source_account.as_deref()),
+            variant_name(&item.status),
+            escape(&item.name)
+        );
+    }
+    xml.push_str("</sources>");
+    xml
+}
+
+fn zone_xml(zone: &Zone) -> String {
+    if zone.members.is_empty() {
+        return "<zone />".to_string();
+    }
+    let mut xml = format!(
+        r#"<zone master="{}"{}>"#,
+        escape(&zone.master),
+        optional_attribute("senderIPAddress", zone.sender_ip_address.as_deref())
+    );
+    for member in &zone.members {
+        let _ = write!(
+            xml,
+            r#"<member ipaddress="{}">{}</member>"#,
+            escape(&member.ip_address),
+            escape(&member.mac_address)
+        );
+    }
+    xml.push_str("</zone>");
+    xml
+}
+
+fn bass_xml(device_id: &str, bass: &Bass) -> String {
+    format!(
+        r#"<bass deviceID="{}"><targetbass>{}</targetbass><actualbass>{}</actualbass></bass>"#,
+        escape(device_id),
+        bass.target,
+        bass.actual
+    )
+}
+
+fn bass_capabilities_xml(device_id: &str, capabilities: &BassCapabilities) -> String {
+    format!(
+        r#"<bassCapabilities deviceID="{}"><bassAvailable>true</bassAvailable><bassMin>{}</bassMin><bassMax>{}</bassMax><bassDefault>{}</bassDefault></bassCapabilities>"#,
+        escape(device_id),
+        capabilities.min_value,
+        capabilities.max_value,
+        capabilities.default
+    )
+}
@@ -1,5 +1,6 @@
 //! Error types for the Bose SoundTouch API
 
+use crate::DeviceErrors;
 use quick_xml::errors::serialize::SeError;
 use thiserror::Error;
 
@@ -10,6 +11,10 @@ pub enum BoseError {
     #[error("Invalid Preset")]
     InvalidPreset(String),
 
+    /// Invalid volume level was specified (valid range: 0-100)
+    #[error("Invalid volume {0} (valid range: 0-100)")]
+    InvalidVolume(i32),
+
     /// Failed to deserialize XML data
     #[error("Failed to deserialize from XML")]
     XmlError(#[from] quick_xml::DeError),
@@ -22,6 +27,34 @@ pub enum BoseError {
     #[error("Http client issue")]
     HttpClientError(#[from] reqwest::Error),
 
+    /// I/O error, e.g. while binding a socket
+    #[error("I/O error: {0}")]
+    IoError(#[from] std::io::Error),
+
+    /// Device answered with a non-success HTTP status code
+    #[error("HTTP status {status} from {url}: {body}")]
+    HttpStatus {
+        /// HTTP status code
+        status: u16,
+        /// Requested URL
+        url: String,
+        /// Start of the response body
+        body: String,
+    },
+
+    /// Device rejected the request with an `<errors>` response
+    #[error("Device returned an error: {0}")]
+    DeviceError(DeviceErrors),
+
+    /// Preset backup was written by a newer, unsupported format version
+    #[error("Unsupported preset backup version {0}")]
+    UnsupportedBackupVersion(u32),
+
+    /// Failed to serialize or deserialize JSON data
+    #[cfg(feature = "json")]
+    #[error("JSON error: {0}")]
+    JsonError(#[from] serde_json::Error),
+
     /// Failed to establish WebSocket connection
     #[cfg(feature = "websocket")]
     #[error("Failed to connect to WebSocket: {0}")]
@@ -37,11 +70,37 @@ pub enum BoseError {
     #[error("Protocol error: {0}")]
     ProtocolError(String),
 
+    /// Device did not answer a keepalive ping in time
+    #[cfg(feature = "websocket")]
+    #[error("No pong received within {0:?}")]
+    PongTimeout(std::time::Duration),
+
+    /// Device did not answer a WebSocket request in time
+    #[cfg(feature = "websocket")]
+    #[error("No response within {0:?}")]
+    ResponseTimeout(std::time::Duration),
+
     /// WebSocket connection was closed
     #[cfg(feature = "websocket")]
     #[error("WebSocket closed")]
     WebSocketClosed,
 }
 
+impl BoseError {
+    /// Whether the error is likely temporary, so that repeating the request may succeed
+    ///
+    /// This covers connection failures, timeouts and HTTP status codes that indicate
+    /// a busy or temporarily unavailable device (408, 429 and 5xx).
+    pub fn is_transient(&self) -> bool {
+        match self {
+            BoseError::HttpClientError(e) => e.is_connect() || e.is_timeout() || e.is_request(),
+            BoseError::HttpStatus { status, .. } => {
+                matches!(status, 408 | 429) || (500..600).contains(status)
+            }
+            _ => false,
+        }
+    }
+}
+
 /// Result type for SoundTouch API operations
 pub type Result<T> = std::result::Result<T, BoseError>;
@@ -0,0 +1,591 @@
+//! In-memory fake of a SoundTouch device for tests
+
+use crate::{
+    unix_time, validate_preset_id, Bass, BassCapabilities, BoseError, Component, Components,
+    ContentItem, DeviceInfo, KeyValue, NetworkInfo, NowPlaying, PlayStatus, Preset, Presets,
+    Recents, RepeatMode, Result, ShuffleMode, SoundTouchApi, Source, SourceItem, SourceStatus,
+    Sources, Status, Volume, VolumeLevel, Zone, ZoneMember,
+};
+use std::collections::VecDeque;
+use std::sync::{Mutex, MutexGuard};
+
+/// A call made on a [`FakeSoundTouch`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Call {
+    GetInfo,
+    SetName(String),
+    GetStatus,
+    GetTrackInfo,
+    GetVolume,
+    SetVolume(i32),
+    GetPresets,
+    GetRecents,
+    SetPreset(i32),
+    StorePreset {
+        id: i32,
+        content_item: ContentItem,
+    },
+    RemovePreset(i32),
+    GetSources,
+    SelectSource {
+        source: String,
+        source_account: Option<String>,
+    },
+    GetZone,
+    SetZone(Vec<(String, String)>),
+    AddZoneSlave {
+        ip_address: String,
+        mac_address: String,
+    },
+    RemoveZoneSlave {
+        ip_address: String,
+        mac_address: String,
+    },
+    GetBassCapabilities,
+    GetBass,
+    SetBass(i32),
+    Key(KeyValue),
+    VolumeUp(u8),
+    VolumeDown(u8),
+    SetMute(bool),
+}
+
+/// State of a [`FakeSoundTouch`]
+#[derive(Debug, Clone)]
+pub struct FakeState {
+    /// Device information returned by `get_info`
+    pub info: DeviceInfo,
+    /// Playback information returned by `get_status` and `get_track_info`
+    pub now_playing: NowPlaying,
+    /// Volume settings returned by `get_volume`
+    pub volume: Volume,
+    /// Highest level `set_volume` will set, see
+    /// [`BoseClient::set_max_volume`](crate::BoseClient::set_max_volume)
+    pub max_volume: Option<VolumeLevel>,
+    /// Presets returned by `get_presets`
+    pub presets: Presets,
+    /// Recently played items returned by `get_recents`
+    pub recents: Recents,
+    /// Sources returned by `get_sources`
+    pub sources: Sources,
+    /// Zone returned by `get_zone`
+    pub zone: Zone,
+    /// Bass settings returned by `get_bass`
+    pub bass: Bass,
+    /// Bass capabilities returned by `get_bass_capabilities`
+    pub bass_capabilities: BassCapabilities,
+}
+
+const FAKE_DEVICE_ID: &str = "000C8AB02519";
+const FAKE_IP_ADDRESS: &str = "127.0.0.1";
+
+impl Default for FakeState {
+    fn default() -> Self {
+        Self {
+            info: DeviceInfo {
+                device_id: FAKE_DEVICE_ID.to_string(),
+                name: "Fake SoundTouch".to_string(),
+                device_type: "SoundTouch 20".to_string(),
+                marge_account_uuid: String::new(),
+                components: Components {
+                    component: vec![Component {
+                        category: "SCM".to_string(),
+                        software_version: Some("27.0.6".to_string()),
+                        serial_number: None,
+                    }],
+                },
+                marge_url: String::new(),
+                network_info: vec![NetworkInfo {
+                    network_type: "SCM".to_string(),
+                    mac_address: FAKE_DEVICE_ID.to_string(),
+                    ip_address: FAKE_IP_ADDRESS.to_string(),
+                }],
+                module_type: "sm2".to_string(),
+                variant: "spotty".to_string(),
+                variant_mode: "normal".to_string(),
+                country_code: "GB".to_string(),
+                region_code: "GB".to_string(),
+            },
+            now_playing: standby(FAKE_DEVICE_ID),
+            volume: Volume {
+                target: 20,
+                actual: 20,
+                mute_enabled: Some(false),
+            },
+            max_volume: None,
+            presets: Presets { items: Vec::new() },
+            recents: Recents::default(),
+            sources: Sources {
+                items: vec![
+                    SourceItem {
+                        source: "AUX".to_string(),
+                        source_account: Some("AUX".to_string()),
+                        status: SourceStatus::Ready,
+                        name: "AUX IN".to_string(),
+                    },
+                    SourceItem {
+                        source: "BLUETOOTH".to_string(),
+                        source_account: None,
+                        status: SourceStatus::Ready,
+                        name: String::new(),
+                    },
+                ],
+            },
+            zone: Zone {
+                master: FAKE_DEVICE_ID.to_string(),
+                sender_ip_address: None,
+                members: Vec::new(),
+            },
+            bass: Bass {
+                target: 0,
+                actual: 0,
+            },
+            bass_capabilities: BassCapabilities {
+                min_value: -9,
+                max_value: 0,
+                default: 0,
+            },
+        }
+    }
+}
+
+/// Now playing information of a device in standby
+fn standby(device_id: &str) -> NowPlaying {
+    playing(
+        device_id,
+        ContentItem {
+            source: Source::Standby,
+            content_type: None,
+            location: None,
+            source_account: None,
+            is_presetable: false,
+            name: None,
+            container_art: None,
+        },
+    )
+}
+
+fn playing(device_id: &str, content_item: ContentItem) -> NowPlaying {
+    let play_status = (content_item.source != Source::Standby).then_some(PlayStatus::PlayState);
+    NowPlaying {
+        device_id: device_id.to_string(),
+        source: content_item.source.clone(),
+        source_account: content_item.source_account.clone(),
+        track: None,
+        artist: None,
+        album: None,
+        station_name: content_item.name.clone(),
+        art: None,
+        play_status,
+        shuffle_setting: None,
+        repeat_setting: None,
+        description: None,
+        station_location: None,
+        stream_type: None,
+        favorite_enabled: None,
+        content_item,
+    }
+}
+
+/// In-memory implementation of [`SoundTouchApi`] for tests
+///
+/// The fake keeps a [`FakeState`] that is returned by the getters and modified by
+/// the setters and key presses, and records every [`Call`] made on it.
+///
+/// # Example
+/// ```
+/// use bose_soundtouch::{Call, FakeSoundTouch, SoundTouchApi};
+///
+/// # tokio_test::block_on(async {
+/// let speaker = FakeSoundTouch::new();
+/// speaker.set_volume(42).await.unwrap();
+///
+/// assert_eq!(speaker.get_volume().await.unwrap().actual, 42);
+/// assert_eq!(speaker.calls(), vec![Call::SetVolume(42), Call::GetVolume]);
+/// # });
+/// ```
+#[derive(Debug, Default)]
+pub struct FakeSoundTouch {
+    state: Mutex<FakeState>,
+    calls: Mutex<Vec<Call>>,
+    failures: Mutex<VecDeque<BoseError>>,
+}
+
+impl FakeSoundTouch {
+    /// Creates a fake with the default state
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a fake with the given state
+    pub fn with_state(state: FakeState) -> Self {
+        Self {
+            state: Mutex::new(state),
+            ..Self::default()
+        }
+    }
+
+    /// Returns a snapshot of the current state
+    pub fn state(&self) -> FakeState {
+        self.lock_state().clone()
+    }
+
+    /// Modifies the current state
+    pub fn update_state<F: FnOnce(&mut FakeState)>(&self, f: F) {
+        f(&mut self.lock_state())
+    }
+
+    /// Returns all calls made so far, in order
+    pub fn calls(&self) -> Vec<Call> {
+        lock(&self.calls).clone()
+    }
+
+    /// Forgets all recorded calls
+    pub fn clear_calls(&self) {
+        lock(&self.calls).clear()
+    }
+
+    /// Makes the next call fail with the given error
+    ///
+    /// Multiple failures are returned in the order they were queued.
+    pub fn fail_next(&self, error: BoseError) {
+        lock(&self.failures).push_back(error)
+    }
+
+    fn lock_state(&self) -> MutexGuard<'_, FakeState> {
+        lock(&self.state)
+    }
+
+    /// Records a call and returns the queued failure, if any
+    fn record(&self, call: Call) -> Result<()> {
+        lock(&self.calls).push(call);
+        match lock(&self.failures).pop_front() {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    fn recall_preset(&self, id: i32) {
+        let mut state = self.lock_state();
+        if let Some(preset) = state.presets.items.iter().find(|p| p.id == id) {
+            state.now_playing = playing(&state.info.device_id, preset.content_item.clone());
+        }
+    }
+
+    fn select(&self, source: &str, source_account: Option<&str>) -> Result<()> {
+        let source: Source = source.parse()?;
+        let mut state = self.lock_state();
+        let name = state
+            .sources
+            .items
+            .iter()
+            .find(|item| source == *item.source)
+            .map(|item| item.name.clone());
+        state.now_playing = playing(
+            &state.info.device_id,
+            ContentItem {
+                is_presetable: true,
+                source,
+                content_type: None,
+                location: None,
+                source_account: source_account.map(String::from),
+                name,
+                container_art: None,
+            },
+        );
+        Ok(())
+    }
+}
+
+/// Sets the volume, respecting the maximum volume, and returns the level that was set
+fn store_volume(state: &mut FakeState, level: VolumeLevel) -> VolumeLevel {
+    let level = match state.max_volume {
+        Some(max) => level.min(max),
+        None => level,
+    };
+    state.volume.target = level.into();
+    state.volume.actual = state.volume.target;
+    level
+}
+
+/// Status the device replies with to a request on `path`
+fn status(path: &str) -> Status {
+    Status {
+        value: path.to_string(),
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+impl SoundTouchApi for FakeSoundTouch {
+    async fn get_info(&self) -> Result<DeviceInfo> {
+        self.record(Call::GetInfo)?;
+        Ok(self.lock_state().info.clone())
+    }
+
+    async fn set_name(&self, name: &str) -> Result<Status> {
+        self.record(Call::SetName(name.to_string()))?;
+        self.lock_state().info.name = name.to_string();
+        Ok(status("/name"))
+    }
+
+    async fn get_status(&self) -> Result<NowPlaying> {
+        self.record(Call::GetStatus)?;
+        Ok(self.lock_state().now_playing.clone())
+    }
+
+    async fn get_track_info(&self) -> Result<NowPlaying> {
+        self.record(Call::GetTrackInfo)?;
+        Ok(self.lock_state().now_playing.clone())
+    }
+
+    async fn get_volume(&self) -> Result<Volume> {
+        self.record(Call::GetVolume)?;
+        Ok(self.lock_state().volume.clone())
+    }
+
+    async fn set_volume(&self, value: i32) -> Result<Status> {
+        self.record(Call::SetVolume(value))?;
+        let level = VolumeLevel::try_from(value)?;
+        store_volume(&mut self.lock_state(), level);
+        Ok(status("/volume"))
+    }
+
+    async fn volume_up(&self, step: u8) -> Result<VolumeLevel> {
+        self.record(Call::VolumeUp(step))?;
+        let mut state = self.lock_state();
+        let level = state.volume.level().up(step);
+        Ok(store_volume(&mut state, level))
+    }
+
+    async fn volume_down(&self, step: u8) -> Result<VolumeLevel> {
+        self.record(Call::VolumeDown(step))?;
+        let mut state = self.lock_state();
+        let level = state.volume.level().down(step);
+        Ok(store_volume(&mut state, level))
+    }
+
+    async fn get_presets(&self) -> Result<Presets> {
+        self.record(Call::GetPresets)?;
+        Ok(self.lock_state().presets.clone())
+    }
+
+    async fn get_recents(&self) -> Result<Recents> {
+        self.record(Call::GetRecents)?;
+        Ok(self.lock_state().recents.clone())
+    }
+
+    async fn set_preset(&self, value: i32) -> Result<Status> {
+        self.record(Call::SetPreset(value))?;
+        if !(1..=6).contains(&value) {
+            return Err(BoseError::InvalidPreset(format!(
+                "{} is not a valid preset (1-6).",
+                value
+            )));
+        }
+        self.recall_preset(value);
+        Ok(status("/key"))
+    }
+
+    async fn store_preset(&self, id: i32, content_item: &ContentItem) -> Result<Presets> {
+        self.record(Call::StorePreset {
+            id,
+            content_item: content_item.clone(),
+        })?;
+        validate_preset_id(id)?;
+        let mut state = self.lock_state();
+        let presets = &mut state.presets.items;
+        let created_on = presets
+            .iter()
+            .find(|preset| preset.id == id)
+            .map_or_else(unix_time, |preset| preset.created_on);
+        presets.retain(|preset| preset.id != id);
+        presets.push(Preset {
+            id,
+            created_on,
+            updated_on: unix_time(),
+            content_item: content_item.clone(),
+        });
+        presets.sort_by_key(|preset| preset.id);
+        Ok(state.presets.clone())
+    }
+
+    async fn remove_preset(&self, id: i32) -> Result<Presets> {
+        self.record(Call::RemovePreset(id))?;
+        validate_preset_id(id)?;
+        let mut state = self.lock_state();
+        state.presets.items.retain(|preset| preset.id != id);
+        Ok(state.presets.clone())
+    }
+
+    async fn get_sources(&self) -> Result<Sources> {
+        self.record(Call::GetSources)?;
+        Ok(self.lock_state().sources.clone())
+    }
+
+    async fn select_source(&self, source: &str, source_account: Option<&str>) -> Result<Status> {
+        self.record(Call::SelectSource {
+            source: source.to_string(),
+            source_account: source_account.map(String::from),
+        })?;
+        self.select(source, source_account)?;
+        Ok(status("/select"))
+    }
+
+    async fn get_zone(&self) -> Result<Zone> {
+        self.record(Call::GetZone)?;
+        Ok(self.lock_state().zone.clone())
+    }
+
+    async fn set_zone(&self, slave_devices: &[(String, String)]) -> Result<Status> {
+        self.record(Call::SetZone(slave_devices.to_vec()))?;
+        let mut state = self.lock_state();
+        let master = state.info.device_id.clone();
+        let master_ip = state
+            .info
+            .network_info
+            .first()
+            .map(|n| n.ip_address.clone())
+            .unwrap_or_default();
+        let mut members = vec![ZoneMember {
+            ip_address: master_ip,
+            mac_address: master.clone(),
+        }];
+        members.extend(slave_devices.iter().map(|(ip, mac)| ZoneMember {
+            ip_address: ip.clone(),
+            mac_address: mac.clone(),
+        }));
+        state.zone = Zone {
+            master,
+            sender_ip_address: None,
+            members,
+        };
+        Ok(status("/setZone"))
+    }
+
+    async fn add_zone_slave(&self, slave_ip: &str, slave_mac: &str) -> Result<Status> {
+        self.record(Call::AddZoneSlave {
+            ip_address: slave_ip.to_string(),
+            mac_address: slave_mac.to_string(),
+        })?;
+        self.lock_state().zone.members.push(ZoneMember {
+            ip_address: slave_ip.to_string(),
+            mac_address: slave_mac.to_string(),
+        });
+        Ok(status("/addZoneSlave"))
+    }
+
+    async fn remove_zone_slave(&self, slave_ip: &str, slave_mac: &str) -> Result<Status> {
+        self.record(Call::RemoveZoneSlave {
+            ip_address: slave_ip.to_string(),
+            mac_address: slave_mac.to_string(),
+        })?;
+        self.lock_state()
+            .zone
+            .members
+            .retain(|m| m.mac_address != slave_mac);
+        Ok(status("/removeZoneSlave"))
+    }
+
+    async fn get_bass_capabilities(&self) -> Result<BassCapabilities> {
+        self.record(Call::GetBassCapabilities)?;
+        Ok(self.lock_state().bass_capabilities.clone())
+    }
+
+    async fn get_bass(&self) -> Result<Bass> {
+        self.record(Call::GetBass)?;
+        Ok(self.lock_state().bass.clone())
+    }
+
+    async fn set_bass(&self, value: i32) -> Result<Status> {
+        self.record(Call::SetBass(value))?;
+        let mut state = self.lock_state();
+        let value = value.clamp(
+            state.bass_capabilities.min_value,
+            state.bass_capabilities.max_value,
+        );
+        state.bass = Bass {
+            target: value,
+            actual: value,
+        };
+        Ok(status("/bass"))
+    }
+
+    async fn press_and_release_key(&self, key_value: &KeyValue) -> Result<Status> {
+        self.record(Call::Key(*key_value))?;
+        let mut state = self.lock_state();
+        let standby = state.now_playing.source == "STANDBY";
+        match key_value {
+            KeyValue::Power if standby => {
+                drop(state);
+                self.select("AUX", Some("AUX"))?;
+            }
+            KeyValue::Power => {
+                state.now_playing = self::standby(&state.info.device_id);
+            }
+            KeyValue::Mute => {
+                let muted = state.volume.mute_enabled.unwrap_or(false);
+                state.volume.mute_enabled = Some(!muted);
+            }
+            KeyValue::VolumeUp => {
+                let actual = (state.volume.actual + 1).min(100);
+                state.volume.actual = actual;
+                state.volume.target = actual;
+            }
+            KeyValue::VolumeDown => {
+                let actual = (state.volume.actual - 1).max(0);
+                state.volume.actual = actual;
+                state.volume.target = actual;
+            }
+            KeyValue::Play if !standby => {
+                state.now_playing.play_status = Some(PlayStatus::PlayState);
+            }
+            KeyValue::Pause if !standby => {
+                state.now_playing.play_status = Some(PlayStatus::PauseState);
+            }
+            KeyValue::PlayPause if !standby => {
+                let playing = state.now_playing.play_status == Some(PlayStatus::PlayState);
+                state.now_playing.play_status = Some(if playing {
+                    PlayStatus::PauseState
+                } else {
+                    PlayStatus::PlayState
+                });
+            }
+            KeyValue::ShuffleOff | KeyValue::ShuffleOn if !standby => {
+                let on = *key_value == KeyValue::ShuffleOn;
+                state.now_playing.shuffle_setting = Some(ShuffleMode::from(on));
+            }
+            KeyValue::RepeatOff if !standby => {
+                state.now_playing.repeat_setting = Some(RepeatMode::Off);
+            }
+            KeyValue::RepeatOne if !standby => {
+                state.now_playing.repeat_setting = Some(RepeatMode::One);
+            }
+            KeyValue::RepeatAll if !standby => {
+                state.now_playing.repeat_setting = Some(RepeatMode::All);
+            }
+            KeyValue::Stop if !standby => {
+                state.now_playing.play_status = Some(PlayStatus::StopState);
+            }
+            KeyValue::Preset1
+            | KeyValue::Preset2
+            | KeyValue::Preset3
+            | KeyValue::Preset4
+            | KeyValue::Preset5
+            | KeyValue::Preset6 => {
+                drop(state);
+                self.recall_preset(*key_value as i32 - KeyValue::Preset1 as i32 + 1);
+            }
+            _ => {}
+        }
+        Ok(status("/key"))
+    }
+
+    async fn set_mute(&self, muted: bool) -> Result<()> {
+        self.record(Call::SetMute(muted))?;
+        self.lock_state().volume.mute_enabled = Some(muted);
+        Ok(())
+    }
+}
@@ -11,6 +11,7 @@ Add `bose_soundtouch` to your `Cargo.toml`:
 [dependencies]
 bose_soundtouch = { version = "1", features = ["websocket"] }
 tokio = { version = "1", features = ["full"] }
+futures-util = "0.3"
 ```
 
 ## HTTP API Example
@@ -32,28 +33,18 @@ async fn main() {
 # #[cfg(feature = "websocket")]
 use bose_soundtouch::{BoseClient, SoundTouchEvent};
 # #[cfg(feature = "websocket")]
-use tokio;
+use futures_util::StreamExt;
 
 # #[cfg(feature = "websocket")]
 #[tokio::main]
 async fn main() {
-    // Create a new client
-    let mut client = BoseClient::new_from_string("bose-speaker.local");
-
-    // Subscribe to events
-    let mut rx = client.subscribe();
-
-    // Start listening in background
-    let mut ws_client = BoseClient::new_from_string(client.hostname());
-    let _rx = ws_client.subscribe();
-    tokio::spawn(async move {
-        if let Err(e) = ws_client.connect_and_listen().await {
-            eprintln!("WebSocket error: {}", e);
-        }
-    });
+    let client = BoseClient::new_from_string("bose-speaker.local");
+
+    // Listen in the background, reconnecting when the connection is lost
+    let mut events = client.events();
 
     // Handle events
-    while let Ok(event) = rx.recv().await {
+    while let Some(event) = events.next().await {
         match event {
             SoundTouchEvent::NowPlayingUpdated(update) => {
                 println!("Now playing: {} - {}",
@@ -61,7 +52,7 @@ async fn main() {
                     update.now_playing.track.unwrap_or_default());
             }
             SoundTouchEvent::VolumeUpdated(vol) => {
-                println!("Volume: {}", vol.volume.actual_volume);
+                println!("Volume: {}", vol.volume.actual);
             }
             _ => {}
         }
@@ -74,17 +65,55 @@ async fn main() {
 
 */
 
+mod api;
+mod backup;
+mod builder;
+#[cfg(feature = "emulator")]
+mod emulator;
 mod error;
+#[cfg(feature = "fake")]
+mod fake;
+#[cfg(feature = "websocket")]
+mod listener;
+mod retry;
+#[cfg(feature = "websocket")]
+mod session;
+mod state;
+mod sync;
 mod types;
 
+pub use api::SoundTouchApi;
+pub use backup::{
+    diff_presets, ImportOptions, PresetBackup, PresetEntry, SlotAction, SlotDiff, SlotPolicy,
+    PRESET_BACKUP_VERSION,
+};
+pub use builder::BoseClientBuilder;
+#[cfg(feature = "emulator")]
+pub use emulator::Emulator;
 pub use error::{BoseError, Result};
+#[cfg(feature = "fake")]
+pub use fake::{Call, FakeSoundTouch, FakeState};
+#[cfg(feature = "websocket")]
+pub use listener::{EventStream, ListenerHandle, ReconnectPolicy};
+#[cfg(feature = "websocket")]
+use listener::{Keepalive, KeepaliveAction};
+pub use retry::{Backoff, RetryPolicy};
+#[cfg(feature = "websocket")]
+pub use session::WebSocketSession;
+pub use state::{DeviceState, StateChange, Track};
+#[cfg(feature = "websocket")]
+pub use sync::keep_presets_in_sync;
+pub use sync::{sync_presets, SyncOutcome};
 pub use types::*;
 
-use reqwest::{Client, IntoUrl};
+use reqwest::Client;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::fmt::Debug;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
 
 #[cfg(feature = "logging")]
 use log::{error, info};
@@ -94,8 +123,8 @@ use tracing::error;
 
 #[cfg(feature = "websocket")]
 use {
-    futures_util::StreamExt,
-    tokio::sync::broadcast,
+    futures_util::{SinkExt, StreamExt},
+    tokio::sync::{broadcast, mpsc},
     tokio_tungstenite::{
         connect_async,
         tungstenite::{client::IntoClientRequest, protocol::Message},
@@ -103,16 +132,77 @@ use {
     url::Url,
 };
 
+#[cfg(feature = "websocket")]
+type WebSocket =
+    tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>;
+
+/// Publishes an event to all subscribers
+#[cfg(feature = "websocket")]
+fn emit(event_tx: &broadcast::Sender<SoundTouchEvent>, event: SoundTouchEvent) {
+    if let Err(_e) = event_tx.send(event) {
+        #[cfg(feature = "logging")]
+        error!("Failed to send event: {}", _e);
+    }
+}
+
+/// Default port of the SoundTouch HTTP API
+pub const DEFAULT_PORT: u16 = 8090;
+
+/// Default port of the SoundTouch WebSocket API
+pub const DEFAULT_WEBSOCKET_PORT: u16 = 8080;
+
+/// Default interval between keepalive pings on the WebSocket
+#[cfg(feature = "websocket")]
+pub(crate) const DEFAULT_PING_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Default time to wait for the answer to a keepalive ping
+#[cfg(feature = "websocket")]
+pub(crate) const DEFAULT_PONG_TIMEOUT: Duration = Duration::from_secs(10);
+
+fn default_port() -> u16 {
+    DEFAULT_PORT
+}
+
+fn default_websocket_port() -> u16 {
+    DEFAULT_WEBSOCKET_PORT
+}
+
+#[cfg(feature = "websocket")]
+fn default_pong_timeout() -> Duration {
+    DEFAULT_PONG_TIMEOUT
+}
+
 /// Client for interacting with Bose SoundTouch devices
 ///
 /// Provides methods to control playback, volume, presets, and device settings
 /// through the SoundTouch HTTP API.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct BoseClient {
     hostname: String,
+    #[serde(default = "default_port")]
+    port: u16,
+    #[serde(default = "default_websocket_port")]
+    websocket_port: u16,
+    #[serde(skip)]
+    http: Client,
+    #[serde(skip)]
+    retry_policy: RetryPolicy,
+    #[serde(skip)]
+    max_volume: Option<VolumeLevel>,
+    #[serde(skip)]
+    volume_lock: Arc<Mutex<()>>,
     #[cfg(feature = "websocket")]
     #[serde(skip)]
     event_tx: Option<broadcast::Sender<SoundTouchEvent>>,
+    #[cfg(feature = "websocket")]
+    #[serde(skip)]
+    enrich_events: bool,
+    #[cfg(feature = "websocket")]
+    #[serde(skip)]
+    ping_interval: Option<Duration>,
+    #[cfg(feature = "websocket")]
+    #[serde(skip, default = "default_pong_timeout")]
+    pong_timeout: Duration,
 }
 
 impl BoseClient {
@@ -126,26 +216,146 @@ impl BoseClient {
     }
 
     pub fn new_from_str(hostname: &str) -> Self {
-        Self {
-            hostname: hostname.to_string(),
-            #[cfg(feature = "websocket")]
-            event_tx: None,
-        }
+        Self::new_from_string(hostname)
     }
 
     pub fn new_from_string<S: Into<String>>(hostname: S) -> Self {
+        Self::with_http_client(hostname, Client::new())
+    }
+
+    pub(crate) fn with_http_client<S: Into<String>>(hostname: S, http: Client) -> Self {
         Self {
             hostname: hostname.into(),
+            port: DEFAULT_PORT,
+            websocket_port: DEFAULT_WEBSOCKET_PORT,
+            http,
+            retry_policy: RetryPolicy::default(),
+            max_volume: None,
+            volume_lock: Arc::default(),
             #[cfg(feature = "websocket")]
             event_tx: None,
+            #[cfg(feature = "websocket")]
+            enrich_events: false,
+            #[cfg(feature = "websocket")]
+            ping_interval: Some(DEFAULT_PING_INTERVAL),
+            #[cfg(feature = "websocket")]
+            pong_timeout: DEFAULT_PONG_TIMEOUT,
         }
     }
 
+    /// Creates a builder to configure timeouts, ports and the HTTP client
+    ///
+    /// # Arguments
+    /// * `hostname` - IP address or hostname of the SoundTouch device
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use bose_soundtouch::BoseClient;
+    /// # use std::time::Duration;
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = BoseClient::builder("192.168.1.143")
+    ///     .connect_timeout(Duration::from_secs(2))
+    ///     .timeout(Duration::from_secs(5))
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn builder<S: Into<String>>(hostname: S) -> BoseClientBuilder {
+        BoseClientBuilder::new(hostname)
+    }
+
     /// Get the hostname of the device
     pub fn hostname(&self) -> &str {
         &self.hostname
     }
 
+    /// Get the port of the HTTP API
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// Get the port of the WebSocket API
+    pub fn websocket_port(&self) -> u16 {
+        self.websocket_port
+    }
+
+    /// Get the policy used to retry failed requests
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
+    /// Set the policy used to retry failed requests
+    ///
+    /// By default, GET requests are attempted up to 3 times on transient errors
+    /// and POST requests are never retried.
+    pub fn set_retry_policy(&mut self, policy: RetryPolicy) {
+        self.retry_policy = policy;
+    }
+
+    /// Get the highest volume level this client will set
+    pub fn max_volume(&self) -> Option<VolumeLevel> {
+        self.max_volume
+    }
+
+    /// Limit the volume levels this client will set
+    ///
+    /// Levels above the limit passed to [`set_volume`](Self::set_volume),
+    /// [`volume_up`](Self::volume_up) and [`volume_down`](Self::volume_down)
+    /// are lowered to the limit. The limit is enforced by the client only;
+    /// the buttons on the device and the volume keys are not restricted.
+    /// `None` removes the limit, which is the default.
+    pub fn set_max_volume(&mut self, max: Option<VolumeLevel>) {
+        self.max_volume = max;
+    }
+
+    /// Whether bare change notifications are enriched with the current state
+    #[cfg(feature = "websocket")]
+    pub fn enrich_events(&self) -> bool {
+        self.enrich_events
+    }
+
+    /// Enable or disable enrichment of bare change notifications
+    ///
+    /// Several notifications, like `<volumeUpdated/>` or `<bassUpdated/>`, only
+    /// signal that something changed. When enabled, the listener queries the
+    /// device for the new state and emits an event carrying the full data.
+    /// See [`BoseClient::enrich_event`] for details. Disabled by default.
+    #[cfg(feature = "websocket")]
+    pub fn set_enrich_events(&mut self, enabled: bool) {
+        self.enrich_events = enabled;
+    }
+
+    /// Interval between keepalive pings on the WebSocket, if enabled
+    #[cfg(feature = "websocket")]
+    pub fn ping_interval(&self) -> Option<Duration> {
+        self.ping_interval
+    }
+
+    /// Set the interval between keepalive pings on the WebSocket
+    ///
+    /// A connection that does not answer a ping within the
+    /// [pong timeout](Self::set_pong_timeout) is considered dead, which detects
+    /// half-open connections, e.g. after a speaker lost power. Pings are sent
+    /// every 30 seconds by default; `None` disables them.
+    #[cfg(feature = "websocket")]
+    pub fn set_ping_interval(&mut self, interval: Option<Duration>) {
+        self.ping_interval = interval;
+    }
+
+    /// Time to wait for the answer to a keepalive ping
+    #[cfg(feature = "websocket")]
+    pub fn pong_timeout(&self) -> Duration {
+        self.pong_timeout
+    }
+
+    /// Set the time to wait for the answer to a keepalive ping (default: 10s)
+    ///
+    /// When exceeded, the connection is closed with `BoseError::PongTimeout`.
+    #[cfg(feature = "websocket")]
+    pub fn set_pong_timeout(&mut self, timeout: Duration) {
+        self.pong_timeout = timeout;
+    }
+
     /// Subscribe to WebSocket events from the device
     #[cfg(feature = "websocket")]
     pub fn subscribe(&mut self) -> broadcast::Receiver<SoundTouchEvent> {
@@ -157,9 +367,51 @@ impl BoseClient {
     }
 
     /// Connect to the WebSocket and start listening for events
+    ///
+    /// Returns when the connection is closed. See
+    /// [`listen_with_reconnect`](Self::listen_with_reconnect) for a listener
+    /// that reconnects automatically.
+    ///
+    /// # Errors
+    /// Returns `BoseError::PongTimeout` if the device stopped answering
+    /// keepalive pings, see [`set_ping_interval`](Self::set_ping_interval).
     #[cfg(feature = "websocket")]
     pub async fn connect_and_listen(&self) -> Result<()> {
-        let url_str = format!("ws://{}:8080", self.hostname);
+        let event_tx = self.event_sender()?;
+        let ws_stream = self.connect_websocket().await?;
+
+        match self.forward_events(ws_stream, &event_tx).await {
+            Ok(true) => {
+                #[cfg(feature = "logging")]
+                info!("WebSocket connection closed by server");
+                emit(&event_tx, SoundTouchEvent::Disconnected);
+            }
+            Ok(false) => {}
+            Err(e @ BoseError::PongTimeout(_)) => {
+                emit(&event_tx, SoundTouchEvent::Disconnected);
+                return Err(e);
+            }
+            Err(_e) => {
+                #[cfg(feature = "logging")]
+                error!("WebSocket error: {}", _e);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the sender used to publish events to subscribers
+    #[cfg(feature = "websocket")]
+    fn event_sender(&self) -> Result<broadcast::Sender<SoundTouchEvent>> {
+        self.event_tx
+            .clone()
+            .ok_or_else(|| BoseError::ProtocolError("No event sender available".to_string()))
+    }
+
+    /// Opens a WebSocket connection to the device using the gabbo protocol
+    #[cfg(feature = "websocket")]
+    async fn connect_websocket(&self) -> Result<WebSocket> {
+        let url_str = format!("ws://{}:{}", self.hostname, self.websocket_port);
         let url = Url::parse(&url_str).map_err(BoseError::UrlParseError)?;
 
         #[cfg(feature = "logging")]
@@ -194,55 +446,177 @@ impl BoseClient {
         #[cfg(feature = "logging")]
         info!("WebSocket connection established with gabbo protocol");
 
-        let (_, mut read) = ws_stream.split();
-        let event_tx = self
-            .event_tx
-            .as_ref()
-            .ok_or_else(|| BoseError::ProtocolError("No event sender available".to_string()))?
-            .clone();
+        Ok(ws_stream)
+    }
+
+    /// Publishes the events received on a WebSocket connection until it ends
+    ///
+    /// Returns `true` if the server closed the connection and `false` if the
+    /// stream ended without a close frame. Keepalive pings are sent while
+    /// waiting for messages.
+    #[cfg(feature = "websocket")]
+    async fn forward_events(
+        &self,
+        ws_stream: WebSocket,
+        event_tx: &broadcast::Sender<SoundTouchEvent>,
+    ) -> Result<bool> {
+        if !self.enrich_events {
+            return self
+                .read_events(ws_stream, |event| emit(event_tx, event))
+                .await;
+        }
 
-        while let Some(message) = read.next().await {
-            match message {
-                Ok(Message::Text(text)) => match self.parse_event(&text) {
-                    Ok(event) => {
-                        if let Err(e) = event_tx.send(event) {
+        // Enriching queries the device over HTTP, which must not stall reading
+        // the connection and answering pings. A separate task enriches the
+        // events one by one, so they are still published in order.
+        let (enrich_tx, mut enrich_rx) = mpsc::unbounded_channel();
+        let client = self.clone();
+        let publisher = event_tx.clone();
+        let enricher = tokio::spawn(async move {
+            while let Some(event) = enrich_rx.recv().await {
+                emit(&publisher, client.enrich_or_keep(event).await);
+            }
+        });
+        let result = self
+            .read_events(ws_stream, move |event| {
+                // Only fails once the enricher stopped, which happens after reading
+                let _ = enrich_tx.send(event);
+            })
+            .await;
+        // Publish the events that are still being enriched before returning
+        let _ = enricher.await;
+        result
+    }
+
+    /// Reads a WebSocket connection until it ends, passing every event to `publish`
+    #[cfg(feature = "websocket")]
+    async fn read_events(
+        &self,
+        ws_stream: WebSocket,
+        mut publish: impl FnMut(SoundTouchEvent),
+    ) -> Result<bool> {
+        let (mut write, mut read) = ws_stream.split();
+        let mut keepalive = Keepalive::new(self.ping_interval, self.pong_timeout);
+
+        loop {
+            let message = tokio::select! {
+                message = read.next() => match message {
+                    Some(message) => message.map_err(BoseError::ConnectionError)?,
+                    None => break,
+                },
+                action = keepalive.tick() => {
+                    match action {
+                        KeepaliveAction::Ping => write
+                            .send(Message::Ping(Default::default()))
+                            .await
+                            .map_err(BoseError::ConnectionError)?,
+                        KeepaliveAction::TimedOut => {
                             #[cfg(feature = "logging")]
-                            error!("Failed to send event: {}", e);
+                            error!("No pong received within {:?}", self.pong_timeout);
+                            let _ = write.close().await;
+                            return Err(BoseError::PongTimeout(self.pong_timeout));
                         }
                     }
-                    Err(e) => {
+                    continue;
+                }
+            };
+            match message {
+                Message::Pong(_) => keepalive.pong_received(),
+                Message::Text(text) => match self.parse_events(&text) {
+                    Ok(events) => events.into_iter().for_each(&mut publish),
+                    Err(_e) => {
                         #[cfg(feature = "logging")]
-                        error!("Failed to parse event: {}", e);
+                        error!("Failed to parse event: {}", _e);
                     }
                 },
-                Ok(Message::Close(_)) => {
-                    #[cfg(feature = "logging")]
-                    info!("WebSocket connection closed by server");
-                    if let Err(e) = event_tx.send(SoundTouchEvent::Disconnected) {
-                        #[cfg(feature = "logging")]
-                        error!("Failed to send disconnect event: {}", e);
-                    }
-                    break;
-                }
-                Err(e) => {
-                    #[cfg(feature = "logging")]
-                    error!("WebSocket error: {}", e);
-                    break;
-                }
+                Message::Close(_) => return Ok(true),
                 _ => {}
             }
         }
 
-        Ok(())
+        Ok(false)
+    }
+
+    /// Fetch the data a bare change notification refers to
+    ///
+    /// Bare notifications are turned into their full counterparts by calling
+    /// the matching getter:
+    /// * `VolumeChangeNotified` - [`get_volume`](Self::get_volume)
+    /// * `NowPlayingChangeNotified` - [`get_status`](Self::get_status)
+    /// * `BassUpdated(None)` - [`get_bass`](Self::get_bass)
+    /// * `ZoneUpdated(None)` - [`get_zone`](Self::get_zone)
+    /// * `SourcesUpdated(None)` - [`get_sources`](Self::get_sources)
+    /// * `InfoUpdated(None)` - [`get_info`](Self::get_info)
+    ///
+    /// Any other event is returned unchanged.
+    ///
+    /// # Errors
+    /// Returns the error of the getter if the device could not be queried
+    #[cfg(feature = "websocket")]
+    pub async fn enrich_event(&self, event: SoundTouchEvent) -> Result<SoundTouchEvent> {
+        Ok(match event {
+            SoundTouchEvent::VolumeChangeNotified => SoundTouchEvent::VolumeUpdated(VolumeUpdate {
+                volume: self.get_volume().await?,
+            }),
+            SoundTouchEvent::NowPlayingChangeNotified => {
+                SoundTouchEvent::NowPlayingUpdated(NowPlayingUpdate {
+                    now_playing: self.get_status().await?,
+                })
+            }
+            SoundTouchEvent::BassUpdated(None) => {
+                SoundTouchEvent::BassUpdated(Some(self.get_bass().await?))
+            }
+            SoundTouchEvent::ZoneUpdated(None) => {
+                SoundTouchEvent::ZoneUpdated(Some(self.get_zone().await?))
+            }
+            SoundTouchEvent::SourcesUpdated(None) => {
+                SoundTouchEvent::SourcesUpdated(Some(self.get_sources().await?))
+            }
+            SoundTouchEvent::InfoUpdated(None) => {
+                SoundTouchEvent::InfoUpdated(Some(self.get_info().await?))
+            }
+            event => event,
+        })
+    }
+
+    /// Enriches an event, falling back to the bare event if the device could not be queried
+    #[cfg(feature = "websocket")]
+    async fn enrich_or_keep(&self, event: SoundTouchEvent) -> SoundTouchEvent {
+        match self.enrich_event(event.clone()).await {
+            Ok(enriched) => enriched,
+            Err(_e) => {
+                #[cfg(feature = "logging")]
+                error!("Failed to enrich event {:?}: {}", event, _e);
+                event
+            }
+        }
     }
 
     /// Parse a WebSocket event from XML
+    ///
+    /// Only the first update of an `<updates>` message is returned.
     #[cfg(feature = "websocket")]
+    #[deprecated(
+        since = "2.2.0",
+        note = "Use parse_events, which returns every update of a message"
+    )]
     pub fn parse_event(&self, xml: &str) -> Result<SoundTouchEvent> {
+        self.parse_events(xml)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| BoseError::ProtocolError("Unknown update type".to_string()))
+    }
+
+    /// Parse all WebSocket events carried by an XML message
+    ///
+    /// The firmware may batch several notifications in a single `<updates>`
+    /// message; one event is returned for each of them.
+    #[cfg(feature = "websocket")]
+    pub fn parse_events(&self, xml: &str) -> Result<Vec<SoundTouchEvent>> {
         // First try to parse device info
         if xml.contains("SoundTouchSdkInfo") {
             return match quick_xml::de::from_str::<SdkInfo>(xml) {
-                Ok(info) => Ok(SoundTouchEvent::DeviceInfo(info)),
+                Ok(info) => Ok(vec![SoundTouchEvent::DeviceInfo(info)]),
                 Err(e) => {
                     #[cfg(feature = "logging")]
                     error!("Failed to parse SdkInfo: {}", e);
@@ -254,7 +628,7 @@ impl BoseClient {
         // Then try user activity
         if xml.contains("userActivityUpdate") {
             return match quick_xml::de::from_str::<UserActivity>(xml) {
-                Ok(activity) => Ok(SoundTouchEvent::UserActivity(activity)),
+                Ok(activity) => Ok(vec![SoundTouchEvent::UserActivity(activity)]),
                 Err(e) => {
                     #[cfg(feature = "logging")]
                     error!("Failed to parse UserActivity: {}", e);
@@ -267,20 +641,13 @@ impl BoseClient {
         if xml.contains("<updates") {
             match quick_xml::de::from_str::<Updates>(xml) {
                 Ok(updates) => {
-                    // Convert the update to an event
-                    if let Some(volume) = updates.volume_updated {
-                        Ok(SoundTouchEvent::VolumeUpdated(volume))
-                    } else if let Some(now_playing) = updates.now_playing_updated {
-                        Ok(SoundTouchEvent::NowPlayingUpdated(now_playing))
-                    } else if let Some(recents) = updates.recents_updated {
-                        Ok(SoundTouchEvent::RecentsUpdated(recents))
-                    } else if let Some(connection) = updates.connection_state_updated {
-                        Ok(SoundTouchEvent::ConnectionStateUpdated(connection))
-                    } else {
+                    let events = updates.into_events();
+                    if events.is_empty() {
                         #[cfg(feature = "logging")]
                         error!("Unknown update type in: {}", xml);
-                        Err(BoseError::ProtocolError("Unknown update type".to_string()))
+                        return Err(BoseError::ProtocolError("Unknown update type".to_string()));
                     }
+                    Ok(events)
                 }
                 Err(e) => {
                     #[cfg(feature = "logging")]
@@ -298,25 +665,38 @@ impl BoseClient {
     }
 
     /// Stops playback
-    pub async fn stop(&self) -> Result<()> {
+    pub async fn stop(&self) -> Result<Status> {
         self.press_and_release_key(&KeyValue::Stop).await
     }
 
     /// Skips to next track
-    pub async fn next_track(&self) -> Result<()> {
+    pub async fn next_track(&self) -> Result<Status> {
         self.press_and_release_key(&KeyValue::NextTrack).await
     }
 
     /// Returns to previous track
-    pub async fn prev_track(&self) -> Result<()> {
+    pub async fn prev_track(&self) -> Result<Status> {
         self.press_and_release_key(&KeyValue::PrevTrack).await
     }
 
     /// Toggles mute state
-    pub async fn mute(&self) -> Result<()> {
+    pub async fn mute(&self) -> Result<Status> {
         self.press_and_release_key(&KeyValue::Mute).await
     }
 
+    /// Mutes or unmutes the device
+    ///
+    /// The device only offers a key that toggles mute, so the current state is
+    /// read first and the key is pressed only if it differs from `muted`.
+    pub async fn set_mute(&self, muted: bool) -> Result<()> {
+        let _guard = self.volume_lock.lock().await;
+        let volume = self.get_volume().await?;
+        if volume.mute_enabled.unwrap_or(false) != muted {
+            self.mute().await?;
+        }
+        Ok(())
+    }
+
     /// Gets information about the device
     pub async fn get_info(&self) -> Result<DeviceInfo> {
         let url = "/info".to_string();
@@ -327,9 +707,9 @@ impl BoseClient {
     ///
     /// # Arguments
     /// * `name` - New name for the device
-    pub async fn set_name(&self, name: &str) -> Result<()> {
-        let url = format!("http://{}:8090/name", &self.hostname);
-        post_xml(
+    pub async fn set_name(&self, name: &str) -> Result<Status> {
+        let url = "/name".to_string();
+        self.post_xml(
             &url,
             &DeviceName {
                 name: name.to_string(),
@@ -339,17 +719,17 @@ impl BoseClient {
     }
 
     /// Starts playback
-    pub async fn play(&self) -> Result<()> {
+    pub async fn play(&self) -> Result<Status> {
         self.press_and_release_key(&KeyValue::Play).await
     }
 
     /// Pauses playback
-    pub async fn pause(&self) -> Result<()> {
+    pub async fn pause(&self) -> Result<Status> {
         self.press_and_release_key(&KeyValue::Pause).await
     }
 
     /// Toggles power state
-    pub async fn power(&self) -> Result<()> {
+    pub async fn power(&self) -> Result<Status> {
         self.press_and_release_key(&KeyValue::Power).await
     }
 
@@ -357,11 +737,49 @@ impl BoseClient {
     ///
     /// # Arguments
     /// * `key_value` - The key to simulate pressing
-    pub async fn press_and_release_key(&self, key_value: &KeyValue) -> Result<()> {
-        let url = format!("http://{}:8090/key", &self.hostname);
-        post_xml(&url, &PostKey::press(key_value)).await?;
-        post_xml(&url, &PostKey::release(key_value)).await?;
-        Ok(())
+    ///
+    /// # Returns
+    /// The status the device sent in reply to the release
+    pub async fn press_and_release_key(&self, key_value: &KeyValue) -> Result<Status> {
+        self.press_key(key_value).await?;
+        self.release_key(key_value).await
+    }
+
+    /// Sends only the press of a key
+    ///
+    /// The key stays pressed until [`release_key`](Self::release_key) is called.
+    pub async fn press_key(&self, key_value: &KeyValue) -> Result<Status> {
+        let url = "/key".to_string();
+        self.post_xml(&url, &PostKey::press(key_value)).await
+    }
+
+    /// Sends only the release of a key
+    pub async fn release_key(&self, key_value: &KeyValue) -> Result<Status> {
+        let url = "/key".to_string();
+        self.post_xml(&url, &PostKey::release(key_value)).await
+    }
+
+    /// Holds a key for the given duration, like a long press on the remote
+    ///
+    /// If the returned future is dropped while waiting, the key stays pressed
+    /// until [`release_key`](Self::release_key) is called.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use bose_soundtouch::{BoseClient, KeyValue};
+    /// # use std::time::Duration;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = BoseClient::new("192.168.1.143");
+    ///
+    /// // A long press on a preset key stores the current station in that slot
+    /// client.hold_key(&KeyValue::Preset1, Duration::from_secs(2)).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn hold_key(&self, key_value: &KeyValue, duration: Duration) -> Result<Status> {
+        self.press_key(key_value).await?;
+        tokio::time::sleep(duration).await;
+        self.release_key(key_value).await
     }
 
     /// Gets the current playback status
@@ -370,6 +788,29 @@ impl BoseClient {
         self.get_xml(&url).await
     }
 
+    /// Gets information about the current track
+    ///
+    /// Returns the same information as [`get_status`](Self::get_status), but
+    /// queries the lighter `/trackInfo` endpoint which does not trigger any
+    /// artwork downloads on the device.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use bose_soundtouch::BoseClient;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = BoseClient::new_from_string("192.168.1.143");
+    /// let track = client.get_track_info().await?;
+    /// println!("{} - {}",
+    ///     track.artist.unwrap_or_default(),
+    ///     track.track.unwrap_or_default());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_track_info(&self) -> Result<NowPlaying> {
+        let url = "/trackInfo".to_string();
+        self.get_xml(&url).await
+    }
+
     /// Gets the current volume settings
     pub async fn get_volume(&self) -> Result<Volume> {
         let url = "/volume".to_string();
@@ -378,12 +819,78 @@ impl BoseClient {
 
     /// Sets the volume level
     ///
+    /// Levels above the [maximum volume](Self::set_max_volume) are lowered to
+    /// the maximum. A running [`volume_up`](Self::volume_up) or
+    /// [`volume_down`](Self::volume_down) finishes before the level is set.
+    ///
     /// # Arguments
     /// * `value` - Volume level (0-100)
-    pub async fn set_volume(&self, value: i32) -> Result<()> {
-        let url = format!("http://{}:8090/volume", &self.hostname);
-        post_xml(&url, &PostVolume::new(value)).await?;
-        Ok(())
+    ///
+    /// # Errors
+    /// Returns `BoseError::InvalidVolume` if the level is not between 0 and 100
+    pub async fn set_volume(&self, value: i32) -> Result<Status> {
+        let level = self.limit_volume(VolumeLevel::try_from(value)?);
+        let _guard = self.volume_lock.lock().await;
+        self.post_volume(level).await
+    }
+
+    /// Sets the volume level, respecting the maximum volume
+    ///
+    /// Returns the level that was actually set.
+    pub async fn set_volume_level(&self, level: VolumeLevel) -> Result<VolumeLevel> {
+        let _guard = self.volume_lock.lock().await;
+        self.store_volume(level).await
+    }
+
+    fn limit_volume(&self, level: VolumeLevel) -> VolumeLevel {
+        match self.max_volume {
+            Some(max) => level.min(max),
+            None => level,
+        }
+    }
+
+    /// Sets the volume without taking `volume_lock`, for callers holding it
+    async fn store_volume(&self, level: VolumeLevel) -> Result<VolumeLevel> {
+        let level = self.limit_volume(level);
+        self.post_volume(level).await?;
+        Ok(level)
+    }
+
+    async fn post_volume(&self, level: VolumeLevel) -> Result<Status> {
+        let url = "/volume".to_string();
+        self.post_xml(&url, &PostVolume::new(level.into())).await
+    }
+
+    /// Raises the volume by `step` levels and returns the new level
+    ///
+    /// The current level is read and the new one set while holding a lock
+    /// shared by all clones of this client, so concurrent steps are not lost.
+    /// The result is capped at 100 and the [maximum volume](Self::set_max_volume).
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use bose_soundtouch::{BoseClient, VolumeLevel};
+    /// # async fn example() -> bose_soundtouch::Result<()> {
+    /// let mut client = BoseClient::new_from_string("192.168.1.143");
+    /// client.set_max_volume(Some(VolumeLevel::new(40)));
+    /// let level = client.volume_up(5).await?;
+    /// println!("Volume: {}", level);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn volume_up(&self, step: u8) -> Result<VolumeLevel> {
+        let _guard = self.volume_lock.lock().await;
+        let current = self.get_volume().await?.level();
+        self.store_volume(current.up(step)).await
+    }
+
+    /// Lowers the volume by `step` levels and returns the new level
+    ///
+    /// Works like [`volume_up`](Self::volume_up); the result is at least 0.
+    pub async fn volume_down(&self, step: u8) -> Result<VolumeLevel> {
+        let _guard = self.volume_lock.lock().await;
+        let current = self.get_volume().await?.level();
+        self.store_volume(current.down(step)).await
     }
 
     /// Gets the list of presets
@@ -392,6 +899,12 @@ impl BoseClient {
         self.get_xml(&url).await
     }
 
+    /// Gets the list of recently played items
+    pub async fn get_recents(&self) -> Result<Recents> {
+        let url = "/recents".to_string();
+        self.get_xml(&url).await
+    }
+
     /// Selects a preset
     ///
     /// # Arguments
@@ -399,7 +912,7 @@ impl BoseClient {
     ///
     /// # Errors
     /// Returns `BoseClientError::InvalidPreset` if the preset number is not between 1 and 6
-    pub async fn set_preset(&self, value: i32) -> Result<()> {
+    pub async fn set_preset(&self, value: i32) -> Result<Status> {
         match value {
             1 => self.press_and_release_key(&KeyValue::Preset1).await,
             2 => self.press_and_release_key(&KeyValue::Preset2).await,
@@ -414,6 +927,98 @@ impl BoseClient {
         }
     }
 
+    /// Stores a content item in a preset slot
+    ///
+    /// Any content already stored in the slot is replaced.
+    ///
+    /// # Arguments
+    /// * `id` - Preset number (1-6)
+    /// * `content_item` - Content to store, e.g. taken from [`NowPlaying`] or [`Recent`]
+    ///
+    /// # Returns
+    /// The presets of the device after the change
+    ///
+    /// # Errors
+    /// Returns `BoseClientError::InvalidPreset` if the preset number is not between 1 and 6
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use bose_soundtouch::BoseClient;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = BoseClient::new("192.168.1.143");
+    /// let now_playing = client.get_status().await?;
+    /// let presets = client.store_preset(1, &now_playing.content_item).await?;
+    /// println!("{} presets", presets.items.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn store_preset(&self, id: i32, content_item: &ContentItem) -> Result<Presets> {
+        validate_preset_id(id)?;
+        let url = "/storePreset".to_string();
+        let now = unix_time();
+        let preset = Preset {
+            id,
+            created_on: now,
+            updated_on: now,
+            content_item: content_item.clone(),
+        };
+        self.post_xml_for(&url, &preset).await
+    }
+
+    /// Clears a preset slot
+    ///
+    /// # Arguments
+    /// * `id` - Preset number (1-6)
+    ///
+    /// # Returns
+    /// The presets of the device after the change
+    ///
+    /// # Errors
+    /// Returns `BoseClientError::InvalidPreset` if the preset number is not between 1 and 6
+    pub async fn remove_preset(&self, id: i32) -> Result<Presets> {
+        validate_preset_id(id)?;
+        let url = "/removePreset".to_string();
+        self.post_xml_for(&url, &PresetSlot { id }).await
+    }
+
+    /// Swaps the contents of two preset slots
+    ///
+    /// Swapping with an empty slot moves the preset there.
+    ///
+    /// # Arguments
+    /// * `a`, `b` - Preset numbers (1-6)
+    ///
+    /// # Returns
+    /// The presets of the device after the change
+    ///
+    /// # Errors
+    /// Returns `BoseClientError::InvalidPreset` if a preset number is not between 1 and 6
+    pub async fn swap_presets(&self, a: i32, b: i32) -> Result<Presets> {
+        SoundTouchApi::swap_presets(self, a, b).await
+    }
+
+    /// Exports the presets of the device to a portable backup
+    pub async fn export_presets(&self) -> Result<PresetBackup> {
+        SoundTouchApi::export_presets(self).await
+    }
+
+    /// Applies a preset backup to the device
+    ///
+    /// Only slots that differ from the backup are changed, following the
+    /// [`SlotPolicy`] of each slot. With [`ImportOptions::dry_run`] nothing is
+    /// changed and the returned diff shows what would happen.
+    ///
+    /// # Errors
+    /// Returns an error if the backup is invalid (see [`PresetBackup::validate`])
+    /// or if storing or removing a preset fails.
+    pub async fn import_presets(
+        &self,
+        backup: &PresetBackup,
+        options: &ImportOptions,
+    ) -> Result<Vec<SlotDiff>> {
+        SoundTouchApi::import_presets(self, backup, options).await
+    }
+
     /// Gets the list of available sources
     pub async fn get_sources(&self) -> Result<Sources> {
         let url = "/sources".to_string();
@@ -440,19 +1045,50 @@ impl BoseClient {
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn select_source(&self, source: &str, source_account: Option<&str>) -> Result<()> {
-        let url = format!("http://{}:8090/select", &self.hostname);
+    pub async fn select_source(
+        &self,
+        source: &str,
+        source_account: Option<&str>,
+    ) -> Result<Status> {
+        let url = "/select".to_string();
         let source_data = SelectSource {
             source: source.to_string(),
             source_account: source_account.map(String::from),
         };
-        post_xml(&url, &source_data).await
+        self.post_xml(&url, &source_data).await
+    }
+
+    /// Starts playing a content item
+    ///
+    /// The item is sent to `/select` as is, so items taken from the presets,
+    /// the recents or the now playing information can be played again.
+    pub async fn select_content_item(&self, content_item: &ContentItem) -> Result<Status> {
+        let url = "/select".to_string();
+        self.post_xml(&url, content_item).await
+    }
+
+    /// Plays a recently played item again
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use bose_soundtouch::BoseClient;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = BoseClient::new("192.168.1.143");
+    /// let recents = client.get_recents().await?;
+    /// if let Some(last) = recents.recent.first() {
+    ///     client.play_recent(last).await?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn play_recent(&self, recent: &Recent) -> Result<Status> {
+        self.select_content_item(&recent.content_item).await
     }
 
     /// Selects the Bluetooth source
     ///
     /// This is a convenience method for selecting the Bluetooth input.
-    pub async fn select_bluetooth(&self) -> Result<()> {
+    pub async fn select_bluetooth(&self) -> Result<Status> {
         self.select_source("BLUETOOTH", None).await
     }
 
@@ -463,7 +1099,7 @@ impl BoseClient {
     ///
     /// # Arguments
     /// * `input` - Optional AUX input number (e.g., None for "AUX", Some("AUX1") for specific input)
-    pub async fn select_aux(&self, input: Option<&str>) -> Result<()> {
+    pub async fn select_aux(&self, input: Option<&str>) -> Result<Status> {
         let source_account = input.unwrap_or("AUX");
         self.select_source("AUX", Some(source_account)).await
     }
@@ -527,8 +1163,8 @@ impl BoseClient {
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn set_zone(&self, slave_devices: &[(String, String)]) -> Result<()> {
-        let url = format!("http://{}:8090/setZone", &self.hostname);
+    pub async fn set_zone(&self, slave_devices: &[(String, String)]) -> Result<Status> {
+        let url = "/setZone".to_string();
 
         // Get the master device info
         let info = self.get_info().await?;
@@ -552,7 +1188,7 @@ impl BoseClient {
             members,
         };
 
-        post_xml(&url, &zone).await
+        self.post_xml(&url, &zone).await
     }
 
     /// Adds a slave device to the existing zone
@@ -576,8 +1212,8 @@ impl BoseClient {
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn add_zone_slave(&self, slave_ip: &str, slave_mac: &str) -> Result<()> {
-        let url = format!("http://{}:8090/addZoneSlave", &self.hostname);
+    pub async fn add_zone_slave(&self, slave_ip: &str, slave_mac: &str) -> Result<Status> {
+        let url = "/addZoneSlave".to_string();
 
         // Get current zone to get master info
         let current_zone = self.get_zone().await?;
@@ -595,7 +1231,7 @@ impl BoseClient {
             members: vec![new_member],
         };
 
-        post_xml(&url, &zone).await
+        self.post_xml(&url, &zone).await
     }
 
     /// Removes a slave device from the zone
@@ -619,8 +1255,8 @@ impl BoseClient {
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn remove_zone_slave(&self, slave_ip: &str, slave_mac: &str) -> Result<()> {
-        let url = format!("http://{}:8090/removeZoneSlave", &self.hostname);
+    pub async fn remove_zone_slave(&self, slave_ip: &str, slave_mac: &str) -> Result<Status> {
+        let url = "/removeZoneSlave".to_string();
 
         // Get current zone to get master info
         let current_zone = self.get_zone().await?;
@@ -638,7 +1274,7 @@ impl BoseClient {
             members: vec![member],
         };
 
-        post_xml(&url, &zone).await
+        self.post_xml(&url, &zone).await
     }
 
     /// Checks if this device is part of a multi-room zone
@@ -719,9 +1355,9 @@ impl BoseClient {
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn set_bass(&self, value: i32) -> Result<()> {
-        let url = format!("http://{}:8090/bass", &self.hostname);
-        post_xml(&url, &SetBass { value }).await
+    pub async fn set_bass(&self, value: i32) -> Result<Status> {
+        let url = "/bass".to_string();
+        self.post_xml(&url, &SetBass { value }).await
     }
 
     /// Sets the bass level to the device's default value
@@ -735,7 +1371,7 @@ impl BoseClient {
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn reset_bass_to_default(&self) -> Result<()> {
+    pub async fn reset_bass_to_default(&self) -> Result<Status> {
         let caps = self.get_bass_capabilities().await?;
         self.set_bass(caps.default).await
     }
@@ -757,10 +1393,9 @@ impl BoseClient {
         let caps = self.get_bass_capabilities().await?;
         let current = self.get_bass().await?;
         if current.actual < caps.max_value {
-            self.set_bass(current.actual + 1).await
-        } else {
-            Ok(())
+            self.set_bass(current.actual + 1).await?;
         }
+        Ok(())
     }
 
     /// Decreases the bass level by one step
@@ -780,81 +1415,208 @@ impl BoseClient {
         let caps = self.get_bass_capabilities().await?;
         let current = self.get_bass().await?;
         if current.actual > caps.min_value {
-            self.set_bass(current.actual - 1).await
-        } else {
-            Ok(())
+            self.set_bass(current.actual - 1).await?;
         }
+        Ok(())
     }
 
     /// Toggles between play and pause states
-    pub async fn play_pause(&self) -> Result<()> {
-        // First try to play
-        let result = self.press_and_release_key(&KeyValue::Play).await;
-        if result.is_err() {
-            // If play fails, try pause
-            self.press_and_release_key(&KeyValue::Pause).await
+    ///
+    /// Sends the `PLAY_PAUSE` key, leaving the decision to the device. Use
+    /// [`toggle_playback`](Self::toggle_playback) to know which state to expect.
+    pub async fn play_pause(&self) -> Result<Status> {
+        self.press_and_release_key(&KeyValue::PlayPause).await
+    }
+
+    /// Pauses when playing and plays otherwise, based on the current play status
+    ///
+    /// # Returns
+    /// The play status the device is expected to switch to
+    pub async fn toggle_playback(&self) -> Result<PlayStatus> {
+        let playing = matches!(
+            self.get_status().await?.play_status,
+            Some(PlayStatus::PlayState | PlayStatus::BufferingState)
+        );
+        if playing {
+            self.pause().await?;
+            Ok(PlayStatus::PauseState)
         } else {
-            Ok(())
+            self.play().await?;
+            Ok(PlayStatus::PlayState)
         }
     }
 
+    /// Toggles playback like [`toggle_playback`](Self::toggle_playback) and
+    /// waits until the device confirms the new state
+    ///
+    /// A WebSocket connection is opened before the key is sent, and the call
+    /// returns the first now playing update with the expected play status.
+    /// Notifications that arrive without details are checked with
+    /// [`get_status`](Self::get_status).
+    ///
+    /// # Errors
+    /// Returns `BoseError::ResponseTimeout` if the device does not confirm the
+    /// change within `timeout`.
+    #[cfg(feature = "websocket")]
+    pub async fn toggle_playback_and_wait(&self, timeout: Duration) -> Result<NowPlaying> {
+        let ws_stream = self.connect_websocket().await?;
+        let (event_tx, mut event_rx) = broadcast::channel(100);
+        let confirmed = async {
+            let expected = self.toggle_playback().await?;
+            loop {
+                match event_rx.recv().await {
+                    Ok(SoundTouchEvent::NowPlayingUpdated(update))
+                        if update.now_playing.play_status == Some(expected) =>
+                    {
+                        return Ok(update.now_playing);
+                    }
+                    // The update could not be enriched or was missed, so
+                    // fetch the status
+                    Ok(SoundTouchEvent::NowPlayingChangeNotified)
+                    | Err(broadcast::error::RecvError::Lagged(_)) => {
+                        let now_playing = self.get_status().await?;
+                        if now_playing.play_status == Some(expected) {
+                            return Ok(now_playing);
+                        }
+                    }
+                    Ok(_) => {}
+                    Err(broadcast::error::RecvError::Closed) => {
+                        return Err(BoseError::WebSocketClosed)
+                    }
+                }
+            }
+        };
+        let result = tokio::time::timeout(timeout, async {
+            tokio::select! {
+                result = confirmed => result,
+                result = self.forward_events(ws_stream, &event_tx) => {
+                    result.and(Err(BoseError::WebSocketClosed))
+                }
+            }
+        })
+        .await;
+        result.unwrap_or(Err(BoseError::ResponseTimeout(timeout)))
+    }
+
+    /// Enables or disables shuffle
+    ///
+    /// Only sources that support shuffle, such as Spotify or stored music,
+    /// react to this. The current setting is reported in
+    /// [`NowPlaying::shuffle_setting`].
+    pub async fn set_shuffle(&self, on: bool) -> Result<Status> {
+        self.press_and_release_key(&ShuffleMode::from(on).key())
+            .await
+    }
+
+    /// Sets the repeat mode
+    ///
+    /// Only sources that support repeat react to this. The current setting is
+    /// reported in [`NowPlaying::repeat_setting`].
+    pub async fn set_repeat(&self, mode: RepeatMode) -> Result<Status> {
+        self.press_and_release_key(&mode.key()).await
+    }
+
     /// Gives thumbs up to current track
-    pub async fn thumbs_up(&self) -> Result<()> {
+    pub async fn thumbs_up(&self) -> Result<Status> {
         self.press_and_release_key(&KeyValue::ThumbsUp).await
     }
 
     /// Gives thumbs down to current track
-    pub async fn thumbs_down(&self) -> Result<()> {
+    pub async fn thumbs_down(&self) -> Result<Status> {
         self.press_and_release_key(&KeyValue::ThumbsDown).await
     }
 
     /// Bookmarks the current track/station
-    pub async fn bookmark(&self) -> Result<()> {
+    pub async fn bookmark(&self) -> Result<Status> {
         self.press_and_release_key(&KeyValue::Bookmark).await
     }
 
     /// Adds current item to favorites
-    pub async fn add_favorite(&self) -> Result<()> {
+    pub async fn add_favorite(&self) -> Result<Status> {
         self.press_and_release_key(&KeyValue::AddFavorite).await
     }
 
     /// Removes current item from favorites
-    pub async fn remove_favorite(&self) -> Result<()> {
+    pub async fn remove_favorite(&self) -> Result<Status> {
         self.press_and_release_key(&KeyValue::RemoveFavorite).await
     }
 
-    #[cfg(feature = "logging")]
+    /// Builds the HTTP URL for a path of the device API
+    fn url(&self, path: &str) -> String {
+        format!("http://{}:{}{}", self.hostname, self.port, path)
+    }
+
     async fn get_xml<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
-        let url = format!("http://{}:8090{}", self.hostname, path);
-        let response = Client::new()
+        self.retry_policy
+            .run(true, || self.get_xml_once(path))
+            .await
+    }
+
+    async fn get_xml_once<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        let url = self.url(path);
+        let response = self
+            .http
             .get(&url)
             .send()
             .await
             .map_err(BoseError::HttpClientError)?;
-        let body = response.text().await?;
+        let body = read_body(response).await?;
+        #[cfg(feature = "logging")]
         log::debug!("Response from {}: {}", url, body);
-        let value: T = quick_xml::de::from_str(&body).map_err(BoseError::XmlError)?;
-        Ok(value)
+        parse_response(&body)
     }
 
-    #[cfg(not(feature = "logging"))]
-    async fn get_xml<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
-        let url = format!("http://{}:8090{}", self.hostname, path);
-        let response = Client::new()
-            .get(&url)
+    async fn post_xml<T: ?Sized + Serialize + Debug>(
+        &self,
+        path: &str,
+        data: &T,
+    ) -> Result<Status> {
+        let body = self.post_xml_raw(path, data).await?;
+        parse_status(&body)
+    }
+
+    /// Posts a request and deserializes the response, for requests that answer with a model
+    async fn post_xml_for<R: DeserializeOwned, T: ?Sized + Serialize + Debug>(
+        &self,
+        path: &str,
+        data: &T,
+    ) -> Result<R> {
+        let body = self.post_xml_raw(path, data).await?;
+        quick_xml::de::from_str(&body).map_err(BoseError::XmlError)
+    }
+
+    /// Posts a request and returns the response body, failing on device errors
+    async fn post_xml_raw<T: ?Sized + Serialize + Debug>(
+        &self,
+        path: &str,
+        data: &T,
+    ) -> Result<String> {
+        let body = serialize_xml(data)?;
+        self.retry_policy
+            .run(false, || self.post_xml_once(path, body.clone()))
+            .await
+    }
+
+    async fn post_xml_once(&self, path: &str, body: String) -> Result<String> {
+        let url = self.url(path);
+        let response = self
+            .http
+            .post(&url)
+            .body(body)
             .send()
             .await
             .map_err(BoseError::HttpClientError)?;
-        let body = response.text().await?;
-        let value: T = quick_xml::de::from_str(&body).map_err(BoseError::XmlError)?;
-        Ok(value)
+        let body = read_body(response).await?;
+        #[cfg(feature = "logging")]
+        log::debug!("Response from {}: {}", url, body);
+        check_device_errors(&body)?;
+        Ok(body)
     }
 }
 
 /// Remote control key values supported by the SoundTouch API
-#[derive(Debug, Serialize, Deserialize, Copy, Clone)]
+#[derive(Debug, Serialize, Deserialize, Copy, Clone, PartialEq, Eq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-#[allow(deprecated)] // Silence warnings for deprecated variants
 pub enum KeyValue {
     Play = 0,
     Pause = 1,
@@ -884,20 +1646,26 @@ pub enum KeyValue {
     AddFavorite = 23,
     #[serde(rename = "REMOVE_FAVORITE")]
     RemoveFavorite = 24,
-    #[deprecated(since = "2.1.0", note = "Use other methods instead")]
+    /// Switches to the AUX input
     AuxInput = 25,
-    #[deprecated(since = "2.1.0", note = "Use other methods instead")]
+    /// Disables shuffle, see [`BoseClient::set_shuffle`]
     ShuffleOff = 26,
-    #[deprecated(since = "2.1.0", note = "Use other methods instead")]
+    /// Enables shuffle, see [`BoseClient::set_shuffle`]
     ShuffleOn = 27,
-    #[deprecated(since = "2.1.0", note = "Use other methods instead")]
+    /// Disables repeat, see [`BoseClient::set_repeat`]
     RepeatOff = 28,
-    #[deprecated(since = "2.1.0", note = "Use other methods instead")]
+    /// Repeats the current track, see [`BoseClient::set_repeat`]
     RepeatOne = 29,
-    #[deprecated(since = "2.1.0", note = "Use other methods instead")]
+    /// Repeats all tracks, see [`BoseClient::set_repeat`]
     RepeatAll = 30,
-    #[deprecated(since = "2.1.0", note = "Use other methods instead")]
+    /// Toggles between play and pause
     PlayPause = 31,
+    /// Raises the volume by one step
+    VolumeUp = 32,
+    /// Lowers the volume by one step
+    VolumeDown = 33,
+    /// Key that the device does not recognize
+    InvalidKey = 34,
 }
 
 impl fmt::Display for KeyValue {
@@ -920,46 +1688,83 @@ impl fmt::Display for KeyState {
 }
 
 /// Current playback information
-#[derive(Debug, Deserialize)]
-#[serde(rename(deserialize = "nowPlaying"))]
+///
+/// Returned by [`BoseClient::get_status`] and carried by
+/// [`SoundTouchEvent::NowPlayingUpdated`].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename = "nowPlaying")]
 pub struct NowPlaying {
     /// Unique device identifier
     #[serde(rename = "@deviceID")]
     pub device_id: String,
     /// Current source (e.g., INTERNET_RADIO, BLUETOOTH, STANDBY)
     #[serde(rename = "@source")]
-    pub source: String,
+    pub source: Source,
     /// Account associated with the current source
-    #[serde(rename = "@sourceAccount")]
+    #[serde(rename = "@sourceAccount", skip_serializing_if = "Option::is_none")]
     pub source_account: Option<String>,
     /// Details about the current content
     #[serde(rename = "ContentItem")]
-    pub content_item: NowPlayingContentItem,
+    pub content_item: ContentItem,
     /// Current track name
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub track: Option<String>,
     /// Current artist name
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub artist: Option<String>,
     /// Current album name
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub album: Option<String>,
     /// Current station name (for radio sources)
-    #[serde(rename = "stationName")]
+    #[serde(rename = "stationName", skip_serializing_if = "Option::is_none")]
     pub station_name: Option<String>,
     /// Artwork URL and status
-    #[serde(rename = "art")]
+    #[serde(rename = "art", skip_serializing_if = "Option::is_none")]
     pub art: Option<Art>,
     /// Current playback status
-    #[serde(rename = "playStatus")]
+    #[serde(rename = "playStatus", skip_serializing_if = "Option::is_none")]
     pub play_status: Option<PlayStatus>,
+    /// Current shuffle setting, if the source supports shuffle
+    #[serde(
+        rename = "shuffleSetting",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub shuffle_setting: Option<ShuffleMode>,
+    /// Current repeat setting, if the source supports repeat
+    #[serde(
+        rename = "repeatSetting",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub repeat_setting: Option<RepeatMode>,
     /// Description of current content
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     /// Station location (for radio sources)
-    #[serde(rename = "stationLocation")]
+    #[serde(rename = "stationLocation", skip_serializing_if = "Option::is_none")]
     pub station_location: Option<String>,
+    /// Type of stream
+    #[serde(rename = "streamType", skip_serializing_if = "Option::is_none")]
+    pub stream_type: Option<StreamType>,
+    /// Whether content can be favorited
+    #[serde(rename = "favoriteEnabled", skip_serializing_if = "Option::is_none")]
+    pub favorite_enabled: Option<String>,
+}
+
+impl NowPlaying {
+    /// URL of the artwork, if any
+    pub fn art_url(&self) -> Option<&str> {
+        self.art.as_ref().and_then(|art| art.url.as_deref())
+    }
 }
 
 /// Content item details for currently playing media
-#[derive(Debug, Deserialize)]
-#[allow(dead_code)]
+///
+/// This is the shape [`NowPlaying::content_item`] had before 2.2.0. Convert a
+/// [`ContentItem`] with [`From`] to keep using it.
+#[deprecated(since = "2.2.0", note = "Use ContentItem instead")]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct NowPlayingContentItem {
     /// Source of the content (e.g., INTERNET_RADIO)
     #[serde(rename = "@source")]
@@ -981,8 +1786,35 @@ pub struct NowPlayingContentItem {
     pub container_art: Option<String>,
 }
 
+impl From<ContentItem> for NowPlayingContentItem {
+    fn from(item: ContentItem) -> Self {
+        Self {
+            source: item.source.to_string(),
+            content_type: item.content_type,
+            location: item.location,
+            is_presetable: item.is_presetable,
+            name: item.name,
+            container_art: item.container_art,
+        }
+    }
+}
+
+impl From<NowPlayingContentItem> for ContentItem {
+    fn from(item: NowPlayingContentItem) -> Self {
+        Self {
+            source: item.source.into(),
+            content_type: item.content_type,
+            location: item.location,
+            source_account: None,
+            is_presetable: item.is_presetable,
+            name: item.name,
+            container_art: item.container_art,
+        }
+    }
+}
+
 /// Artwork information
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct Art {
     /// Status of the artwork
     #[serde(rename = "@artImageStatus")]
@@ -993,30 +1825,102 @@ pub struct Art {
 }
 
 /// Status of artwork
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum ArtStatus {
+    /// No artwork available
     Invalid,
+    /// Using default artwork
     ShowDefaultImage,
+    /// Artwork is being downloaded
     Downloading,
+    /// Artwork is available and loaded
     ImagePresent,
 }
 
 /// Playback status
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum PlayStatus {
+    /// Content is playing
     PlayState,
+    /// Playback is paused
     PauseState,
+    /// Playback is stopped
     StopState,
+    /// Content is buffering
     BufferingState,
+    /// Invalid play status
     InvalidPlayStatus,
 }
 
+/// Shuffle setting reported in the now playing information
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ShuffleMode {
+    /// Tracks play in order
+    #[serde(rename = "SHUFFLE_OFF")]
+    Off,
+    /// Tracks play in random order
+    #[serde(rename = "SHUFFLE_ON")]
+    On,
+}
+
+impl ShuffleMode {
+    /// Whether shuffle is enabled
+    pub fn is_on(&self) -> bool {
+        *self == ShuffleMode::On
+    }
+
+    /// Key that switches to this mode
+    pub fn key(&self) -> KeyValue {
+        match self {
+            ShuffleMode::Off => KeyValue::ShuffleOff,
+            ShuffleMode::On => KeyValue::ShuffleOn,
+        }
+    }
+}
+
+impl From<bool> for ShuffleMode {
+    fn from(on: bool) -> Self {
+        if on {
+            ShuffleMode::On
+        } else {
+            ShuffleMode::Off
+        }
+    }
+}
+
+/// Repeat setting reported in the now playing information
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum RepeatMode {
+    /// Playback stops at the end
+    #[serde(rename = "REPEAT_OFF")]
+    Off,
+    /// The current track is repeated
+    #[serde(rename = "REPEAT_ONE")]
+    One,
+    /// All tracks are repeated
+    #[serde(rename = "REPEAT_ALL")]
+    All,
+}
+
+impl RepeatMode {
+    /// Key that switches to this mode
+    pub fn key(&self) -> KeyValue {
+        match self {
+            RepeatMode::Off => KeyValue::RepeatOff,
+            RepeatMode::One => KeyValue::RepeatOne,
+            RepeatMode::All => KeyValue::RepeatAll,
+        }
+    }
+}
+
 /// Volume settings for the device
-#[derive(Debug, Deserialize)]
-#[serde(rename(deserialize = "volume"))]
-#[allow(dead_code)]
+///
+/// Returned by [`BoseClient::get_volume`] and carried by
+/// [`SoundTouchEvent::VolumeUpdated`].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename = "volume")]
 pub struct Volume {
     /// Target volume level (0-100)
     #[serde(rename = "targetvolume")]
@@ -1025,10 +1929,87 @@ pub struct Volume {
     #[serde(rename = "actualvolume")]
     pub actual: i32,
     /// Whether mute is enabled
-    #[serde(rename = "muteenabled")]
+    #[serde(rename = "muteenabled", skip_serializing_if = "Option::is_none")]
     pub mute_enabled: Option<bool>,
 }
 
+impl Volume {
+    /// Current actual volume level, clamped to 0-100
+    pub fn level(&self) -> VolumeLevel {
+        VolumeLevel::new(self.actual)
+    }
+
+    /// Target volume level (0-100)
+    #[deprecated(since = "2.2.0", note = "Use the `target` field instead")]
+    pub fn target_volume(&self) -> u8 {
+        self.target.clamp(0, 100) as u8
+    }
+
+    /// Current actual volume level (0-100)
+    #[deprecated(since = "2.2.0", note = "Use the `actual` field instead")]
+    pub fn actual_volume(&self) -> u8 {
+        self.actual.clamp(0, 100) as u8
+    }
+}
+
+/// Volume level between 0 and 100
+///
+/// Use [`VolumeLevel::new`] to clamp arbitrary values into range, or
+/// [`TryFrom<i32>`] to reject values outside of it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct VolumeLevel(u8);
+
+impl VolumeLevel {
+    /// Lowest level (silent)
+    pub const MIN: VolumeLevel = VolumeLevel(0);
+    /// Highest level
+    pub const MAX: VolumeLevel = VolumeLevel(100);
+
+    /// Creates a level, clamping the value to 0-100
+    pub fn new(value: i32) -> Self {
+        VolumeLevel(value.clamp(0, 100) as u8)
+    }
+
+    /// The level as a number between 0 and 100
+    pub fn value(&self) -> u8 {
+        self.0
+    }
+
+    /// Level raised by `step`, capped at 100
+    pub fn up(&self, step: u8) -> Self {
+        Self::new(i32::from(self.0) + i32::from(step))
+    }
+
+    /// Level lowered by `step`, at least 0
+    pub fn down(&self, step: u8) -> Self {
+        Self::new(i32::from(self.0) - i32::from(step))
+    }
+}
+
+impl TryFrom<i32> for VolumeLevel {
+    type Error = BoseError;
+
+    fn try_from(value: i32) -> Result<Self> {
+        if (0..=100).contains(&value) {
+            Ok(VolumeLevel(value as u8))
+        } else {
+            Err(BoseError::InvalidVolume(value))
+        }
+    }
+}
+
+impl From<VolumeLevel> for i32 {
+    fn from(level: VolumeLevel) -> Self {
+        i32::from(level.0)
+    }
+}
+
+impl fmt::Display for VolumeLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 #[derive(Debug, Serialize)]
 #[serde(rename(serialize = "volume"))]
 pub struct PostVolume {
@@ -1042,13 +2023,33 @@ impl PostVolume {
     }
 }
 
+/// Body of a `/removePreset` request
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename = "preset")]
+pub(crate) struct PresetSlot {
+    #[serde(rename = "@id")]
+    pub(crate) id: i32,
+}
+
+/// Checks that a preset number is between 1 and 6
+pub(crate) fn validate_preset_id(id: i32) -> Result<()> {
+    if (1..=6).contains(&id) {
+        Ok(())
+    } else {
+        Err(BoseError::InvalidPreset(format!(
+            "{} is not a valid preset (1-6).",
+            id
+        )))
+    }
+}
+
 #[derive(Debug, Serialize)]
 #[serde(rename = "key")]
 pub struct PostKey<'a> {
     #[serde(rename = "$text")]
     value: &'a KeyValue,
     #[serde(rename = "@state")]
-    state: &'static str,
+    state: KeyState,
     #[serde(rename = "@sender")]
     sender: &'static str,
 }
@@ -1057,7 +2058,7 @@ impl<'a> PostKey<'a> {
     pub fn press(key: &'a KeyValue) -> Self {
         Self {
             value: key,
-            state: "press",
+            state: KeyState::Press,
             sender: "Gabbo",
         }
     }
@@ -1065,16 +2066,15 @@ impl<'a> PostKey<'a> {
     pub fn release(value: &'a KeyValue) -> Self {
         Self {
             value,
-            state: "release",
+            state: KeyState::Release,
             sender: "Gabbo",
         }
     }
 }
 
 /// Collection of preset stations/sources
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename(deserialize = "nowPlaying"))]
-#[allow(dead_code)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename = "presets")]
 pub struct Presets {
     /// List of preset items
     #[serde(rename = "$value", default)]
@@ -1082,24 +2082,30 @@ pub struct Presets {
 }
 
 /// Individual preset station/source
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename = "preset")]
 pub struct Preset {
     /// Preset number (1-6)
     #[serde(rename = "@id")]
     pub id: i32,
     /// Unix timestamp when preset was created
-    #[serde(rename = "@createdOn")]
+    #[serde(rename = "@createdOn", default)]
     pub created_on: i32,
     /// Unix timestamp when preset was last updated
-    #[serde(rename = "@updatedOn")]
+    #[serde(rename = "@updatedOn", default)]
     pub updated_on: i32,
     /// Content details for this preset
-    #[serde(rename = "$value")]
-    pub content_item: PresetContentItem,
+    #[serde(rename = "ContentItem")]
+    pub content_item: ContentItem,
 }
 
 /// Content details for a preset
-#[derive(Debug, Serialize, Deserialize)]
+///
+/// This is the shape [`Preset::content_item`] had before 2.2.0. Convert a
+/// [`ContentItem`] with [`From`] to keep using it; missing values become
+/// empty strings.
+#[deprecated(since = "2.2.0", note = "Use ContentItem instead")]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PresetContentItem {
     /// Source of the preset content
     #[serde(rename = "@source")]
@@ -1124,6 +2130,34 @@ pub struct PresetContentItem {
     pub container_art: String,
 }
 
+impl From<ContentItem> for PresetContentItem {
+    fn from(item: ContentItem) -> Self {
+        Self {
+            source: item.source.to_string(),
+            preset_type: item.content_type.unwrap_or_default(),
+            location: item.location.unwrap_or_default(),
+            source_account: item.source_account.unwrap_or_default(),
+            is_presetable: item.is_presetable,
+            name: item.name.unwrap_or_default(),
+            container_art: item.container_art.unwrap_or_default(),
+        }
+    }
+}
+
+impl From<PresetContentItem> for ContentItem {
+    fn from(item: PresetContentItem) -> Self {
+        Self {
+            source: item.source.into(),
+            content_type: Some(item.preset_type),
+            location: Some(item.location),
+            source_account: Some(item.source_account),
+            is_presetable: item.is_presetable,
+            name: Some(item.name),
+            container_art: Some(item.container_art),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PresetContentItemValue {
     //#[serde(rename = "itemName")]
@@ -1137,23 +2171,154 @@ where
     quick_xml::se::to_string(value).map_err(BoseError::SerializeError)
 }
 
-async fn post_xml<U: IntoUrl + Debug + Clone, T: ?Sized + Serialize + Debug>(
-    url: U,
-    data: &T,
-) -> Result<()> {
-    let client = Client::new();
-    let body = serialize_xml(data)?;
-    client
-        .post(url.clone())
-        .body(body.clone())
-        .send()
-        .await
-        .map_err(BoseError::HttpClientError)?;
-    Ok(())
+/// Current time as a Unix timestamp, as used for the timestamps of presets
+pub(crate) fn unix_time() -> i32 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs() as i32)
+}
+
+/// Maximum number of characters of a response body kept in [`BoseError::HttpStatus`]
+const BODY_SNIPPET_LEN: usize = 256;
+
+/// Returns the body of a response, or [`BoseError::HttpStatus`] if the request was not successful
+async fn read_body(response: reqwest::Response) -> Result<String> {
+    let status = response.status();
+    let url = response.url().to_string();
+    let body = response.text().await?;
+    if !status.is_success() {
+        return Err(BoseError::HttpStatus {
+            status: status.as_u16(),
+            url,
+            body: body.chars().take(BODY_SNIPPET_LEN).collect(),
+        });
+    }
+    Ok(body)
+}
+
+/// Deserializes a response body, turning an `<errors>` reply into [`BoseError::DeviceError`]
+fn parse_response<T: DeserializeOwned>(body: &str) -> Result<T> {
+    check_device_errors(body)?;
+    quick_xml::de::from_str(body).map_err(BoseError::XmlError)
+}
+
+/// Parses the reply to a request without a dedicated return payload
+///
+/// Bodies other than `<status>`, such as an empty body, give an empty status.
+fn parse_status(body: &str) -> Result<Status> {
+    match root_element(body) {
+        Some("status") => quick_xml::de::from_str(body).map_err(BoseError::XmlError),
+        _ => Ok(Status::default()),
+    }
+}
+
+/// Returns an error if the body is an `<errors>` or `<error>` reply from the device
+fn check_device_errors(body: &str) -> Result<()> {
+    match root_element(body) {
+        Some("errors") => {
+            let errors: DeviceErrors =
+                quick_xml::de::from_str(body).map_err(BoseError::XmlError)?;
+            Err(BoseError::DeviceError(errors))
+        }
+        Some("error") => {
+            // Malformed requests get a bare <error> element without attributes
+            let error: DeviceError = quick_xml::de::from_str(body).map_err(BoseError::XmlError)?;
+            Err(BoseError::DeviceError(DeviceErrors {
+                device_id: None,
+                errors: vec![error],
+            }))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Returns the name of the root element of an XML document
+fn root_element(xml: &str) -> Option<&str> {
+    let mut rest = xml.trim_start();
+    loop {
+        rest = rest.strip_prefix('<')?;
+        if rest.starts_with('?') || rest.starts_with('!') {
+            // Skip the XML declaration, comments and doctypes
+            rest = rest[rest.find('>')? + 1..].trim_start();
+            continue;
+        }
+        let end = rest
+            .find(|c: char| c.is_whitespace() || c == '>' || c == '/')
+            .unwrap_or(rest.len());
+        return Some(&rest[..end]);
+    }
+}
+
+/// Default response of the device for requests without a dedicated return payload
+///
+/// Returned by setters like [`BoseClient::set_volume`] or
+/// [`BoseClient::press_key`]. Devices that answer with an empty body produce
+/// an empty status.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+#[serde(rename(deserialize = "status"))]
+pub struct Status {
+    /// Status message, usually the path of the request (e.g. `/key`)
+    #[serde(rename = "$text", default)]
+    pub value: String,
+}
+
+/// Errors reported by the device in response to a request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename = "errors")]
+pub struct DeviceErrors {
+    /// Device ID (MAC address) of the reporting device
+    #[serde(rename = "@deviceID", default)]
+    pub device_id: Option<String>,
+    /// List of reported errors
+    #[serde(rename = "error", default)]
+    pub errors: Vec<DeviceError>,
+}
+
+impl fmt::Display for DeviceErrors {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, error) in self.errors.iter().enumerate() {
+            if i > 0 {
+                write!(f, "; ")?;
+            }
+            write!(f, "{}", error)?;
+        }
+        Ok(())
+    }
+}
+
+/// Individual error reported by the device
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeviceError {
+    /// Numeric error code (e.g. 1019)
+    #[serde(rename = "@value", default)]
+    pub value: i32,
+    /// Symbolic error name (e.g. CLIENT_XML_ERROR)
+    #[serde(rename = "@name", default)]
+    pub name: String,
+    /// Severity of the error
+    #[serde(rename = "@severity", default)]
+    pub severity: String,
+    /// Error message
+    #[serde(rename = "$text", default)]
+    pub message: String,
+}
+
+impl fmt::Display for DeviceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.name.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(
+                f,
+                "{} {} ({}): {}",
+                self.value, self.name, self.severity, self.message
+            )
+        }
+    }
 }
 
 /// Information about the device
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename = "info")]
 pub struct DeviceInfo {
     /// Device ID (MAC address)
@@ -1192,14 +2357,14 @@ pub struct DeviceInfo {
 }
 
 /// Component version information
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct Components {
     /// List of components
     pub component: Vec<Component>,
 }
 
 /// Individual component information
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct Component {
     /// Component category
     #[serde(rename = "componentCategory")]
@@ -1213,7 +2378,7 @@ pub struct Component {
 }
 
 /// Network information for the device
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct NetworkInfo {
     /// Network type (SCM or SMSC)
     #[serde(rename = "@type")]
@@ -1234,8 +2399,8 @@ struct DeviceName {
 }
 
 /// Available sources for the SoundTouch device
-#[derive(Debug, Deserialize)]
-#[serde(rename(deserialize = "sources"))]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename = "sources")]
 pub struct Sources {
     /// List of available sources
     #[serde(rename = "sourceItem")]
@@ -1243,7 +2408,7 @@ pub struct Sources {
 }
 
 /// Individual source item
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct SourceItem {
     /// Source type (e.g., INTERNET_RADIO, BLUETOOTH, AUX)
     #[serde(rename = "@source")]
@@ -1255,12 +2420,12 @@ pub struct SourceItem {
     #[serde(rename = "@status")]
     pub status: SourceStatus,
     /// Display name of the source
-    #[serde(rename = "$value")]
+    #[serde(rename = "$value", default)]
     pub name: String,
 }
 
 /// Status of a source
-#[derive(Debug, Deserialize, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum SourceStatus {
     /// Source is not available
@@ -1271,6 +2436,7 @@ pub enum SourceStatus {
 
 /// Content item for selecting a source
 #[derive(Debug, Serialize)]
+#[serde(rename = "ContentItem")]
 struct SelectSource {
     /// Source type (e.g., INTERNET_RADIO, BLUETOOTH, AUX)
     #[serde(rename = "@source")]
@@ -1281,22 +2447,22 @@ struct SelectSource {
 }
 
 /// Zone configuration for multi-room audio
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
 #[serde(rename = "zone")]
 pub struct Zone {
     /// MAC address of the master device
-    #[serde(rename = "@master")]
+    #[serde(rename = "@master", default)]
     pub master: String,
     /// IP address of the sender (only used when setting zones)
     #[serde(rename = "@senderIPAddress", skip_serializing_if = "Option::is_none")]
     pub sender_ip_address: Option<String>,
     /// List of zone members (master and slaves)
-    #[serde(rename = "member")]
+    #[serde(rename = "member", default)]
     pub members: Vec<ZoneMember>,
 }
 
 /// Member device in a multi-room zone
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
 pub struct ZoneMember {
     /// IP address of the device
     #[serde(rename = "@ipaddress")]
@@ -1307,22 +2473,22 @@ pub struct ZoneMember {
 }
 
 /// Bass capabilities of the device
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 #[serde(rename(deserialize = "bassCapabilities"))]
 pub struct BassCapabilities {
     /// Minimum bass level supported
-    #[serde(rename = "@minValue")]
+    #[serde(rename = "bassMin", alias = "@minValue")]
     pub min_value: i32,
     /// Maximum bass level supported
-    #[serde(rename = "@maxValue")]
+    #[serde(rename = "bassMax", alias = "@maxValue")]
     pub max_value: i32,
     /// Default bass level
-    #[serde(rename = "@default")]
+    #[serde(rename = "bassDefault", alias = "@default")]
     pub default: i32,
 }
 
 /// Bass settings for the device
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename(deserialize = "bass"))]
 pub struct Bass {
     /// Current bass level
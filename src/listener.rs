@@ -0,0 +1,401 @@
+//! WebSocket listener that reconnects after the connection is lost
+
+use crate::{
+    emit, Backoff, BoseClient, NowPlayingUpdate, RecentsUpdate, Result, SoundTouchEvent,
+    VolumeUpdate,
+};
+use futures_util::Stream;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tokio::task::AbortHandle;
+use tokio::time::{sleep_until, Instant};
+
+#[cfg(feature = "logging")]
+use log::{error, info, warn};
+
+/// Schedules keepalive pings and detects pings that were not answered in time
+pub(crate) struct Keepalive {
+    interval: Option<Duration>,
+    pong_timeout: Duration,
+    next_ping: Instant,
+    pong_deadline: Option<Instant>,
+}
+
+/// What to do when [`Keepalive::tick`] completes
+pub(crate) enum KeepaliveAction {
+    /// A ping is due
+    Ping,
+    /// The last ping was not answered in time
+    TimedOut,
+}
+
+impl Keepalive {
+    /// Creates a schedule that pings every `interval`, or never if `None`
+    pub(crate) fn new(interval: Option<Duration>, pong_timeout: Duration) -> Self {
+        Self {
+            interval,
+            pong_timeout,
+            next_ping: Instant::now() + interval.unwrap_or_default(),
+            pong_deadline: None,
+        }
+    }
+
+    /// Waits until a ping is due or the pending ping timed out
+    ///
+    /// Cancelling the returned future leaves the schedule unchanged.
+    pub(crate) async fn tick(&mut self) -> KeepaliveAction {
+        let Some(interval) = self.interval else {
+            return std::future::pending().await;
+        };
+        match self.pong_deadline {
+            Some(deadline) if deadline <= self.next_ping => {
+                sleep_until(deadline).await;
+                KeepaliveAction::TimedOut
+            }
+            _ => {
+                sleep_until(self.next_ping).await;
+                let now = Instant::now();
+                self.next_ping = now + interval;
+                self.pong_deadline.get_or_insert(now + self.pong_timeout);
+                KeepaliveAction::Ping
+            }
+        }
+    }
+
+    /// Records that the device answered a ping
+    pub(crate) fn pong_received(&mut self) {
+        self.pong_deadline = None;
+    }
+}
+
+/// Policy deciding how [`BoseClient::listen_with_reconnect`] reconnects
+///
+/// By default, the listener reconnects forever, starting after 500ms and
+/// backing off exponentially up to 30s between attempts. State is not resynced
+/// after a reconnect unless [`resync`](Self::resync) is enabled.
+///
+/// # Example
+/// ```no_run
+/// # use bose_soundtouch::{BoseClient, ReconnectPolicy};
+/// # use std::time::Duration;
+/// # async fn example() -> bose_soundtouch::Result<()> {
+/// let mut client = BoseClient::new_from_string("192.168.1.143");
+/// let mut events = client.subscribe();
+/// let policy = ReconnectPolicy::new()
+///     .max_backoff(Duration::from_secs(10))
+///     .resync(true);
+/// tokio::spawn(async move { client.listen_with_reconnect(policy).await });
+///
+/// while let Ok(event) = events.recv().await {
+///     println!("{:?}", event);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReconnectPolicy {
+    backoff: Backoff,
+    max_attempts: Option<u32>,
+    resync: bool,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReconnectPolicy {
+    /// Creates a policy that reconnects forever
+    pub fn new() -> Self {
+        Self {
+            backoff: Backoff {
+                initial: Duration::from_millis(500),
+                max: Duration::from_secs(30),
+                multiplier: 2.0,
+                jitter: true,
+            },
+            max_attempts: None,
+            resync: false,
+        }
+    }
+
+    /// Sets the delay before the first reconnect attempt
+    pub fn initial_backoff(mut self, delay: Duration) -> Self {
+        self.backoff.initial = delay;
+        self
+    }
+
+    /// Sets the upper bound for the delay between attempts
+    pub fn max_backoff(mut self, delay: Duration) -> Self {
+        self.backoff.max = delay;
+        self
+    }
+
+    /// Sets the factor by which the delay grows after every attempt
+    pub fn multiplier(mut self, multiplier: f64) -> Self {
+        self.backoff.multiplier = multiplier;
+        self
+    }
+
+    /// Enables or disables randomization of the delay
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.backoff.jitter = jitter;
+        self
+    }
+
+    /// Gives up after `max_attempts` consecutive failed connection attempts
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = Some(max_attempts.max(1));
+        self
+    }
+
+    /// Fetches the current state over HTTP after every reconnect
+    ///
+    /// The now playing information, volume, bass, zone, presets and recents
+    /// are fetched and published as regular update events, so changes made
+    /// while the connection was down are not lost.
+    pub fn resync(mut self, resync: bool) -> Self {
+        self.resync = resync;
+        self
+    }
+
+    /// Backoff between connection attempts
+    pub fn backoff(&self) -> &Backoff {
+        &self.backoff
+    }
+
+    /// Maximum number of consecutive failed connection attempts, if limited
+    pub fn attempts_limit(&self) -> Option<u32> {
+        self.max_attempts
+    }
+
+    /// Whether the state is fetched over HTTP after every reconnect
+    pub fn resyncs(&self) -> bool {
+        self.resync
+    }
+}
+
+impl BoseClient {
+    /// Connect to the WebSocket and keep listening for events, reconnecting
+    /// whenever the connection is lost
+    ///
+    /// Besides the device events, subscribers receive
+    /// [`Connecting`](SoundTouchEvent::Connecting) before the first attempt,
+    /// [`Reconnecting`](SoundTouchEvent::Reconnecting) before every following one,
+    /// [`Connected`](SoundTouchEvent::Connected) once a connection is established
+    /// and [`Disconnected`](SoundTouchEvent::Disconnected) when it is lost.
+    ///
+    /// # Errors
+    /// Returns the last connection error once the attempts allowed by the policy
+    /// are exhausted, or `BoseError::ProtocolError` if nobody subscribed to events.
+    pub async fn listen_with_reconnect(&self, policy: ReconnectPolicy) -> Result<()> {
+        let event_tx = self.event_sender()?;
+        let mut connected_before = false;
+        let mut attempt = 0;
+
+        loop {
+            emit(
+                &event_tx,
+                if attempt == 0 {
+                    SoundTouchEvent::Connecting
+                } else {
+                    SoundTouchEvent::Reconnecting(attempt)
+                },
+            );
+
+            match self.connect_websocket().await {
+                Ok(ws_stream) => {
+                    emit(&event_tx, SoundTouchEvent::Connected);
+                    if connected_before && policy.resync {
+                        self.resync(&event_tx).await;
+                    }
+                    connected_before = true;
+                    attempt = 0;
+
+                    if let Err(_e) = self.forward_events(ws_stream, &event_tx).await {
+                        #[cfg(feature = "logging")]
+                        error!("WebSocket error: {}", _e);
+                    }
+                    emit(&event_tx, SoundTouchEvent::Disconnected);
+                }
+                Err(e) => {
+                    #[cfg(feature = "logging")]
+                    error!("Failed to connect to WebSocket: {}", e);
+                    if policy.max_attempts.is_some_and(|max| attempt + 1 >= max) {
+                        return Err(e);
+                    }
+                }
+            }
+
+            attempt += 1;
+            let delay = policy.backoff.delay(attempt);
+            #[cfg(feature = "logging")]
+            info!("Reconnecting in {:?} (attempt {})", delay, attempt);
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    /// Publishes the current state of the device as update events
+    async fn resync(&self, event_tx: &broadcast::Sender<SoundTouchEvent>) {
+        let events = [
+            self.get_status().await.map(|now_playing| {
+                SoundTouchEvent::NowPlayingUpdated(NowPlayingUpdate { now_playing })
+            }),
+            self.get_volume()
+                .await
+                .map(|volume| SoundTouchEvent::VolumeUpdated(VolumeUpdate { volume })),
+            self.get_bass()
+                .await
+                .map(|bass| SoundTouchEvent::BassUpdated(Some(bass))),
+            self.get_zone()
+                .await
+                .map(|zone| SoundTouchEvent::ZoneUpdated(Some(zone))),
+            self.get_presets()
+                .await
+                .map(SoundTouchEvent::PresetsUpdated),
+            self.get_recents()
+                .await
+                .map(|recents| SoundTouchEvent::RecentsUpdated(RecentsUpdate { recents })),
+        ];
+        for event in events {
+            match event {
+                Ok(event) => emit(event_tx, event),
+                Err(_e) => {
+                    #[cfg(feature = "logging")]
+                    error!("Failed to resync state: {}", _e);
+                }
+            }
+        }
+    }
+}
+
+/// Stream of the events of a device, returned by [`BoseClient::events`]
+///
+/// The stream is fed by a background task that keeps a WebSocket connection
+/// open, reconnecting when it is lost. It ends once the task stops, either
+/// because it was cancelled or because it gave up reconnecting. Dropping the
+/// stream cancels the task.
+pub struct EventStream {
+    inner: Pin<Box<dyn Stream<Item = SoundTouchEvent> + Send>>,
+    handle: ListenerHandle,
+}
+
+impl EventStream {
+    /// Returns a handle that cancels the background connection
+    pub fn handle(&self) -> ListenerHandle {
+        self.handle.clone()
+    }
+}
+
+impl Stream for EventStream {
+    type Item = SoundTouchEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.as_mut().poll_next(cx)
+    }
+}
+
+impl Drop for EventStream {
+    fn drop(&mut self) {
+        self.handle.cancel();
+    }
+}
+
+impl std::fmt::Debug for EventStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventStream")
+            .field("handle", &self.handle)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Handle to the background connection of an [`EventStream`]
+#[derive(Debug, Clone)]
+pub struct ListenerHandle {
+    task: AbortHandle,
+}
+
+impl ListenerHandle {
+    /// Closes the connection and stops reconnecting
+    ///
+    /// The event stream ends after the events received so far.
+    pub fn cancel(&self) {
+        self.task.abort();
+    }
+
+    /// Whether the background connection has stopped
+    pub fn is_finished(&self) -> bool {
+        self.task.is_finished()
+    }
+}
+
+impl BoseClient {
+    /// Stream the events of the device
+    ///
+    /// Spawns a task on the current Tokio runtime that listens on the WebSocket
+    /// with the default [`ReconnectPolicy`]. Unlike
+    /// [`connect_and_listen`](Self::connect_and_listen), this does not require
+    /// calling [`subscribe`](Self::subscribe) first and works on a shared
+    /// client, e.g. one held in an `Arc`.
+    ///
+    /// # Panics
+    /// Panics if called outside of a Tokio runtime.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use bose_soundtouch::{BoseClient, SoundTouchEvent};
+    /// use futures_util::StreamExt;
+    ///
+    /// # async fn example() {
+    /// let client = BoseClient::new_from_string("192.168.1.143");
+    /// let mut events = client.events();
+    /// while let Some(event) = events.next().await {
+    ///     if let SoundTouchEvent::VolumeUpdated(update) = event {
+    ///         println!("Volume: {}", update.volume.actual);
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub fn events(&self) -> EventStream {
+        self.events_with_policy(ReconnectPolicy::default())
+    }
+
+    /// Stream the events of the device, reconnecting according to `policy`
+    ///
+    /// See [`events`](Self::events).
+    pub fn events_with_policy(&self, policy: ReconnectPolicy) -> EventStream {
+        let (event_tx, rx) = broadcast::channel(100);
+        let mut listener = self.clone();
+        listener.event_tx = Some(event_tx);
+
+        let task = tokio::spawn(async move {
+            if let Err(_e) = listener.listen_with_reconnect(policy).await {
+                #[cfg(feature = "logging")]
+                error!("WebSocket listener stopped: {}", _e);
+            }
+        });
+
+        let inner = futures_util::stream::unfold(rx, |mut rx| async move {
+            loop {
+                match rx.recv().await {
+                    Ok(event) => return Some((event, rx)),
+                    Err(broadcast::error::RecvError::Lagged(_skipped)) => {
+                        #[cfg(feature = "logging")]
+                        warn!("Event stream lagged behind, skipped {} events", _skipped);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => return None,
+                }
+            }
+        });
+
+        EventStream {
+            inner: Box::pin(inner),
+            handle: ListenerHandle {
+                task: task.abort_handle(),
+            },
+        }
+    }
+}
@@ -0,0 +1,225 @@
+//! Retry policies for requests to the SoundTouch API
+
+use crate::{BoseError, Result};
+use std::fmt;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[cfg(feature = "logging")]
+use log::debug;
+
+/// Exponential backoff with optional jitter
+///
+/// The delay before attempt `n + 1` is `initial * multiplier^(n - 1)`, capped at `max`.
+/// With jitter enabled, a random delay between half and the full computed value is used.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Backoff {
+    /// Delay after the first failed attempt
+    pub initial: Duration,
+    /// Upper bound for the delay
+    pub max: Duration,
+    /// Factor by which the delay grows after every attempt
+    pub multiplier: f64,
+    /// Whether to randomize the delay
+    pub jitter: bool,
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self {
+            initial: Duration::from_millis(100),
+            max: Duration::from_secs(2),
+            multiplier: 2.0,
+            jitter: true,
+        }
+    }
+}
+
+impl Backoff {
+    /// Returns the delay to wait after the given (1-based) failed attempt
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(i32::MAX as u32) as i32;
+        let delay = self
+            .initial
+            .mul_f64(self.multiplier.max(1.0).powi(exponent).min(u32::MAX as f64))
+            .min(self.max);
+        if self.jitter {
+            delay.mul_f64(0.5 + random_fraction() / 2.0)
+        } else {
+            delay
+        }
+    }
+}
+
+/// Returns a pseudo-random number in `[0, 1)`
+fn random_fraction() -> f64 {
+    use std::collections::hash_map::RandomState;
+    use std::hash::{BuildHasher, Hasher};
+
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u128(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or_default(),
+    );
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Policy deciding whether and how failed requests are retried
+///
+/// By default, GET requests are attempted up to 3 times when they fail with a
+/// transient error (see [`BoseError::is_transient`]). POST requests, such as key
+/// presses, are never retried unless [`retry_non_idempotent`](Self::retry_non_idempotent)
+/// is enabled.
+///
+/// # Example
+/// ```no_run
+/// # use bose_soundtouch::{BoseClient, RetryPolicy};
+/// # use std::time::Duration;
+/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = BoseClient::builder("192.168.1.143")
+///     .retry_policy(
+///         RetryPolicy::new(5)
+///             .initial_backoff(Duration::from_millis(250))
+///             .max_backoff(Duration::from_secs(5)),
+///     )
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    backoff: Backoff,
+    retry_non_idempotent: bool,
+    retryable: Arc<dyn Fn(&BoseError) -> bool + Send + Sync>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new(3)
+    }
+}
+
+impl fmt::Debug for RetryPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("max_attempts", &self.max_attempts)
+            .field("backoff", &self.backoff)
+            .field("retry_non_idempotent", &self.retry_non_idempotent)
+            .finish_non_exhaustive()
+    }
+}
+
+impl RetryPolicy {
+    /// Creates a policy that makes at most `max_attempts` attempts per request
+    pub fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            backoff: Backoff::default(),
+            retry_non_idempotent: false,
+            retryable: Arc::new(BoseError::is_transient),
+        }
+    }
+
+    /// Creates a policy that never retries
+    pub fn none() -> Self {
+        Self::new(1)
+    }
+
+    /// Sets the delay after the first failed attempt
+    pub fn initial_backoff(mut self, delay: Duration) -> Self {
+        self.backoff.initial = delay;
+        self
+    }
+
+    /// Sets the upper bound for the delay between attempts
+    pub fn max_backoff(mut self, delay: Duration) -> Self {
+        self.backoff.max = delay;
+        self
+    }
+
+    /// Sets the factor by which the delay grows after every attempt
+    pub fn multiplier(mut self, multiplier: f64) -> Self {
+        self.backoff.multiplier = multiplier;
+        self
+    }
+
+    /// Enables or disables randomization of the delay
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.backoff.jitter = jitter;
+        self
+    }
+
+    /// Also retries non-idempotent requests such as key presses
+    pub fn retry_non_idempotent(mut self, retry: bool) -> Self {
+        self.retry_non_idempotent = retry;
+        self
+    }
+
+    /// Sets the predicate deciding which errors are retried
+    ///
+    /// # Example
+    /// ```
+    /// # use bose_soundtouch::{BoseError, RetryPolicy};
+    /// // Also retry when the device reports an error
+    /// let policy = RetryPolicy::new(3).retry_if(|e| {
+    ///     e.is_transient() || matches!(e, BoseError::DeviceError(_))
+    /// });
+    /// ```
+    pub fn retry_if<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(&BoseError) -> bool + Send + Sync + 'static,
+    {
+        self.retryable = Arc::new(predicate);
+        self
+    }
+
+    /// Maximum number of attempts per request
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Backoff between attempts
+    pub fn backoff(&self) -> &Backoff {
+        &self.backoff
+    }
+
+    /// Whether the given error should be retried
+    pub fn is_retryable(&self, error: &BoseError) -> bool {
+        (self.retryable)(error)
+    }
+
+    /// Runs `operation` until it succeeds, fails with a non-retryable error or
+    /// runs out of attempts
+    pub(crate) async fn run<T, F, Fut>(&self, idempotent: bool, mut operation: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let max_attempts = if idempotent || self.retry_non_idempotent {
+            self.max_attempts
+        } else {
+            1
+        };
+
+        let mut attempt = 1;
+        loop {
+            match operation().await {
+                Err(e) if attempt < max_attempts && self.is_retryable(&e) => {
+                    let delay = self.backoff.delay(attempt);
+                    #[cfg(feature = "logging")]
+                    debug!(
+                        "Attempt {} of {} failed ({}), retrying in {:?}",
+                        attempt, max_attempts, e, delay
+                    );
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
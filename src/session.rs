@@ -0,0 +1,402 @@
+//! Requests over the gabbo WebSocket connection
+
+use crate::{
+    check_device_errors, emit, parse_response, parse_status, serialize_xml, BoseClient, BoseError,
+    Keepalive, KeepaliveAction, KeyValue, NowPlaying, PostKey, PostVolume, Result, SoundTouchEvent,
+    Status, Volume, VolumeLevel, WebSocket,
+};
+use futures_util::stream::SplitSink;
+use futures_util::{SinkExt, StreamExt};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+use tokio::sync::{broadcast, oneshot};
+use tokio::task::AbortHandle;
+use tokio_tungstenite::tungstenite::protocol::Message;
+
+#[cfg(feature = "logging")]
+use log::{debug, error};
+
+/// Default time to wait for the response to a request
+const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+type Writer = tokio::sync::Mutex<SplitSink<WebSocket, Message>>;
+type Pending = Mutex<Waiters>;
+
+/// Requests that wait for their response, by request id
+#[derive(Default)]
+struct Waiters {
+    responses: HashMap<u32, oneshot::Sender<String>>,
+    /// Set by the reader when the connection ended, after which no request may wait
+    closed: bool,
+}
+
+/// Removes the waiter of a request when dropped
+struct WaiterGuard<'a> {
+    pending: &'a Pending,
+    id: u32,
+}
+
+impl Drop for WaiterGuard<'_> {
+    fn drop(&mut self) {
+        lock(self.pending).responses.remove(&self.id);
+    }
+}
+
+/// Locks the waiters, also after a panic of another holder of the lock
+fn lock(pending: &Pending) -> MutexGuard<'_, Waiters> {
+    pending.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Header of a gabbo `<msg>` envelope
+///
+/// Requests and responses share the same header; the device echoes the
+/// `requestID` of a request in its response.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub(crate) struct GabboHeader {
+    #[serde(rename = "@deviceID", default)]
+    pub(crate) device_id: String,
+    #[serde(rename = "@url")]
+    pub(crate) url: String,
+    #[serde(rename = "@method", default)]
+    pub(crate) method: String,
+    pub(crate) request: GabboRequest,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub(crate) struct GabboRequest {
+    #[serde(rename = "@requestID")]
+    pub(crate) id: u32,
+}
+
+#[derive(Deserialize)]
+struct Envelope {
+    header: GabboHeader,
+}
+
+impl GabboHeader {
+    /// Formats a `<msg>` envelope with this header and an optional body
+    pub(crate) fn to_msg(&self, body: Option<&str>) -> String {
+        format!(
+            r#"<msg><header deviceID="{}" url="{}" method="{}"><request requestID="{}"><info type="new"/></request></header>{}</msg>"#,
+            quick_xml::escape::escape(&self.device_id),
+            quick_xml::escape::escape(&self.url),
+            quick_xml::escape::escape(&self.method),
+            self.request.id,
+            body.map(|b| format!("<body>{}</body>", b))
+                .unwrap_or_default()
+        )
+    }
+}
+
+/// Parses a `<msg>` envelope into its header and the contents of its body
+pub(crate) fn parse_msg(xml: &str) -> Result<(GabboHeader, Option<&str>)> {
+    let envelope: Envelope = quick_xml::de::from_str(xml)?;
+    let body = xml
+        .find("<body>")
+        .zip(xml.rfind("</body>"))
+        .and_then(|(start, end)| xml.get(start + "<body>".len()..end));
+    Ok((envelope.header, body))
+}
+
+/// Connection to the device over which requests are sent
+///
+/// Created by [`BoseClient::connect_session`]. Requests are sent over a single
+/// WebSocket connection using the gabbo protocol and matched with their
+/// responses by request id, so a long-lived controller does not need to open an
+/// HTTP connection for every command. Notifications received on the same
+/// connection are published to the receivers returned by
+/// [`subscribe`](Self::subscribe).
+///
+/// The connection is closed when the session is dropped.
+///
+/// # Example
+/// ```no_run
+/// # use bose_soundtouch::{BoseClient, KeyValue};
+/// # async fn example() -> bose_soundtouch::Result<()> {
+/// let client = BoseClient::new_from_string("192.168.1.143");
+/// let session = client.connect_session().await?;
+///
+/// session.set_volume(30).await?;
+/// session.press_and_release_key(&KeyValue::Play).await?;
+/// println!("{:?}", session.get_status().await?);
+/// # Ok(())
+/// # }
+/// ```
+pub struct WebSocketSession {
+    device_id: String,
+    writer: Arc<Writer>,
+    pending: Arc<Pending>,
+    next_id: AtomicU32,
+    timeout: Duration,
+    max_volume: Option<VolumeLevel>,
+    events: broadcast::Sender<SoundTouchEvent>,
+    reader: AbortHandle,
+}
+
+impl std::fmt::Debug for WebSocketSession {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WebSocketSession")
+            .field("device_id", &self.device_id)
+            .field("timeout", &self.timeout)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for WebSocketSession {
+    fn drop(&mut self) {
+        self.reader.abort();
+    }
+}
+
+impl BoseClient {
+    /// Opens a WebSocket connection for sending requests
+    ///
+    /// The device id used in the request headers is fetched once over HTTP.
+    /// The keepalive settings and the maximum volume of this client apply to
+    /// the session.
+    ///
+    /// # Panics
+    /// Panics if called outside of a Tokio runtime.
+    pub async fn connect_session(&self) -> Result<WebSocketSession> {
+        let device_id = self.get_info().await?.device_id;
+        let (write, read) = self.connect_websocket().await?.split();
+        let writer = Arc::new(tokio::sync::Mutex::new(write));
+        let pending = Arc::new(Mutex::new(Waiters::default()));
+        let (events, _) = broadcast::channel(100);
+
+        let reader = tokio::spawn(read_messages(
+            self.clone(),
+            read,
+            writer.clone(),
+            pending.clone(),
+            events.clone(),
+        ));
+
+        Ok(WebSocketSession {
+            device_id,
+            writer,
+            pending,
+            next_id: AtomicU32::new(1),
+            timeout: DEFAULT_REQUEST_TIMEOUT,
+            max_volume: self.max_volume(),
+            events,
+            reader: reader.abort_handle(),
+        })
+    }
+}
+
+impl WebSocketSession {
+    /// Device id sent in the request headers
+    pub fn device_id(&self) -> &str {
+        &self.device_id
+    }
+
+    /// Time to wait for the response to a request
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Sets the time to wait for the response to a request (default: 5s)
+    ///
+    /// Use [`request`](Self::request) to override it for a single request.
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout;
+    }
+
+    /// Subscribe to the notifications received on this connection
+    pub fn subscribe(&self) -> broadcast::Receiver<SoundTouchEvent> {
+        self.events.subscribe()
+    }
+
+    /// Whether the connection is still open
+    pub fn is_connected(&self) -> bool {
+        !self.reader.is_finished()
+    }
+
+    /// Number of requests waiting for their response
+    pub fn pending_requests(&self) -> usize {
+        lock(&self.pending).responses.len()
+    }
+
+    /// Sends a request and returns the body of the response
+    ///
+    /// # Arguments
+    /// * `method` - `GET` or `POST`
+    /// * `url` - Path of the request, e.g. `volume`
+    /// * `body` - XML body of the request
+    /// * `timeout` - Time to wait for the response
+    ///
+    /// # Errors
+    /// Returns `BoseError::ResponseTimeout` if no response arrived in time and
+    /// `BoseError::WebSocketClosed` if the connection was lost.
+    pub async fn request(
+        &self,
+        method: &str,
+        url: &str,
+        body: Option<&str>,
+        timeout: Duration,
+    ) -> Result<String> {
+        let header = GabboHeader {
+            device_id: self.device_id.clone(),
+            url: url.trim_start_matches('/').to_string(),
+            method: method.to_string(),
+            request: GabboRequest {
+                id: self.next_id.fetch_add(1, Ordering::Relaxed),
+            },
+        };
+        let msg = header.to_msg(body);
+        let (tx, rx) = oneshot::channel();
+        {
+            // The reader marks the waiters as closed under the same lock, so a
+            // request either sees the closed connection or is failed by the reader
+            let mut waiters = lock(&self.pending);
+            if waiters.closed || !self.is_connected() {
+                return Err(BoseError::WebSocketClosed);
+            }
+            waiters.responses.insert(header.request.id, tx);
+        }
+        // Stops waiting when the request ends, also when its future is dropped
+        let _waiter = WaiterGuard {
+            pending: &self.pending,
+            id: header.request.id,
+        };
+
+        #[cfg(feature = "logging")]
+        debug!("Sending request: {}", msg);
+        let sent = self
+            .writer
+            .lock()
+            .await
+            .send(Message::Text(msg.into()))
+            .await;
+        sent.map_err(BoseError::ConnectionError)?;
+
+        match tokio::time::timeout(timeout, rx).await {
+            Ok(Ok(response)) => Ok(response),
+            Ok(Err(_)) => Err(BoseError::WebSocketClosed),
+            Err(_) => Err(BoseError::ResponseTimeout(timeout)),
+        }
+    }
+
+    /// Sends a `GET` request and deserializes the response
+    pub async fn get<T: DeserializeOwned>(&self, url: &str) -> Result<T> {
+        let body = self.request("GET", url, None, self.timeout).await?;
+        parse_response(&body)
+    }
+
+    /// Sends a `POST` request with the given body
+    pub async fn post<T: ?Sized + Serialize>(&self, url: &str, data: &T) -> Result<Status> {
+        let body = self
+            .request("POST", url, Some(&serialize_xml(data)?), self.timeout)
+            .await?;
+        check_device_errors(&body)?;
+        parse_status(&body)
+    }
+
+    /// Presses and releases a key
+    ///
+    /// Returns the status the device sent in reply to the release.
+    pub async fn press_and_release_key(&self, key_value: &KeyValue) -> Result<Status> {
+        self.post("key", &PostKey::press(key_value)).await?;
+        self.post("key", &PostKey::release(key_value)).await
+    }
+
+    /// Gets the current playback status
+    pub async fn get_status(&self) -> Result<NowPlaying> {
+        self.get("now_playing").await
+    }
+
+    /// Gets the current volume settings
+    pub async fn get_volume(&self) -> Result<Volume> {
+        self.get("volume").await
+    }
+
+    /// Sets the volume level (0-100)
+    ///
+    /// Levels above the [maximum volume](BoseClient::set_max_volume) of the
+    /// client that opened the session are lowered to the maximum.
+    ///
+    /// # Errors
+    /// Returns `BoseError::InvalidVolume` if the level is not between 0 and 100
+    pub async fn set_volume(&self, value: i32) -> Result<Status> {
+        let level = VolumeLevel::try_from(value)?;
+        let level = match self.max_volume {
+            Some(max) => level.min(max),
+            None => level,
+        };
+        self.post("volume", &PostVolume::new(level.into())).await
+    }
+}
+
+/// Reads the connection, answering pending requests and publishing notifications
+async fn read_messages(
+    client: BoseClient,
+    mut read: futures_util::stream::SplitStream<WebSocket>,
+    writer: Arc<Writer>,
+    pending: Arc<Pending>,
+    events: broadcast::Sender<SoundTouchEvent>,
+) {
+    let mut keepalive = Keepalive::new(client.ping_interval, client.pong_timeout);
+
+    loop {
+        let message = tokio::select! {
+            message = read.next() => message,
+            action = keepalive.tick() => {
+                let alive = match action {
+                    KeepaliveAction::Ping => writer
+                        .lock()
+                        .await
+                        .send(Message::Ping(Default::default()))
+                        .await
+                        .is_ok(),
+                    KeepaliveAction::TimedOut => {
+                        #[cfg(feature = "logging")]
+                        error!("No pong received within {:?}", client.pong_timeout);
+                        false
+                    }
+                };
+                if alive {
+                    continue;
+                }
+                break;
+            }
+        };
+        match message {
+            Some(Ok(Message::Text(text))) if text.trim_start().starts_with("<msg") => {
+                match parse_msg(&text) {
+                    Ok((header, body)) => {
+                        let waiter = lock(&pending).responses.remove(&header.request.id);
+                        if let Some(waiter) = waiter {
+                            let _ = waiter.send(body.unwrap_or_default().to_string());
+                        }
+                    }
+                    Err(_e) => {
+                        #[cfg(feature = "logging")]
+                        error!("Failed to parse response: {}", _e);
+                    }
+                }
+            }
+            Some(Ok(Message::Text(text))) => match client.parse_events(&text) {
+                Ok(parsed) => parsed.into_iter().for_each(|event| emit(&events, event)),
+                Err(_e) => {
+                    #[cfg(feature = "logging")]
+                    error!("Failed to parse event: {}", _e);
+                }
+            },
+            Some(Ok(Message::Pong(_))) => keepalive.pong_received(),
+            Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+            Some(Ok(_)) => {}
+        }
+    }
+
+    // Dropping the waiters fails the pending requests
+    let mut waiters = lock(&pending);
+    waiters.closed = true;
+    waiters.responses.clear();
+    drop(waiters);
+    emit(&events, SoundTouchEvent::Disconnected);
+}
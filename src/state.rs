@@ -0,0 +1,390 @@
+//! Local mirror of the state of a device
+
+use crate::{
+    Bass, BoseClient, DeviceInfo, NowPlaying, PlayStatus, Presets, Recents, Result,
+    SoundTouchEvent, Source, Sources, Volume, Zone,
+};
+use tokio::sync::{broadcast, watch};
+
+#[cfg(feature = "websocket")]
+use {
+    crate::{NowPlayingUpdate, RecentsUpdate, ReconnectPolicy, VolumeUpdate},
+    futures_util::StreamExt,
+    std::sync::{Arc, Weak},
+    tokio::task::AbortHandle,
+};
+
+/// Identity of the content that is playing
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Track {
+    /// Track name
+    pub track: Option<String>,
+    /// Artist name
+    pub artist: Option<String>,
+    /// Album name
+    pub album: Option<String>,
+    /// Station name (for radio sources)
+    pub station_name: Option<String>,
+}
+
+impl From<&NowPlaying> for Track {
+    fn from(now_playing: &NowPlaying) -> Self {
+        Self {
+            track: now_playing.track.clone(),
+            artist: now_playing.artist.clone(),
+            album: now_playing.album.clone(),
+            station_name: now_playing.station_name.clone(),
+        }
+    }
+}
+
+/// What changed between two snapshots of the device state
+///
+/// Derived from [`SoundTouchEvent::NowPlayingUpdated`] and
+/// [`SoundTouchEvent::VolumeUpdated`] by comparing the new value with the
+/// previous one. Use [`DeviceState::changes`] to receive them as they happen,
+/// or [`StateChange::now_playing`] and [`StateChange::volume`] to compare
+/// snapshots yourself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StateChange {
+    /// Different content started playing
+    TrackChanged {
+        /// Previous content
+        from: Track,
+        /// New content
+        to: Track,
+    },
+    /// Playback status changed, e.g. from paused to playing
+    PlayStatusChanged {
+        /// Previous status
+        from: Option<PlayStatus>,
+        /// New status
+        to: Option<PlayStatus>,
+    },
+    /// Another source was selected
+    SourceChanged {
+        /// Previous source
+        from: Source,
+        /// New source
+        to: Source,
+    },
+    /// Mute was toggled; contains whether the device is muted now
+    MuteChanged(bool),
+    /// Actual volume level changed
+    VolumeChanged {
+        /// Previous level
+        from: i32,
+        /// New level
+        to: i32,
+        /// Difference between the new and the previous level
+        delta: i32,
+    },
+}
+
+impl StateChange {
+    /// Changes between two now playing snapshots
+    ///
+    /// Source changes come first, followed by track and play status changes.
+    pub fn now_playing(from: &NowPlaying, to: &NowPlaying) -> Vec<StateChange> {
+        let mut changes = Vec::new();
+        if from.source != to.source {
+            changes.push(StateChange::SourceChanged {
+                from: from.source.clone(),
+                to: to.source.clone(),
+            });
+        }
+        let (from_track, to_track) = (Track::from(from), Track::from(to));
+        if from_track != to_track {
+            changes.push(StateChange::TrackChanged {
+                from: from_track,
+                to: to_track,
+            });
+        }
+        if from.play_status != to.play_status {
+            changes.push(StateChange::PlayStatusChanged {
+                from: from.play_status,
+                to: to.play_status,
+            });
+        }
+        changes
+    }
+
+    /// Changes between two volume snapshots
+    ///
+    /// A missing mute flag counts as not muted.
+    pub fn volume(from: &Volume, to: &Volume) -> Vec<StateChange> {
+        let mut changes = Vec::new();
+        let muted = to.mute_enabled.unwrap_or(false);
+        if from.mute_enabled.unwrap_or(false) != muted {
+            changes.push(StateChange::MuteChanged(muted));
+        }
+        if from.actual != to.actual {
+            changes.push(StateChange::VolumeChanged {
+                from: from.actual,
+                to: to.actual,
+                delta: to.actual - from.actual,
+            });
+        }
+        changes
+    }
+}
+
+/// Mirror of the state of a device that is kept up to date by events
+///
+/// The state is loaded once over HTTP and then updated incrementally from
+/// [`SoundTouchEvent`]s. Every part of the state is published through a
+/// [`watch`] channel, so consumers can wait for changes instead of polling.
+///
+/// Use [`follow`](Self::follow) to keep the state in sync with the WebSocket
+/// events of the device, or [`apply`](Self::apply) to feed it events yourself.
+///
+/// # Example
+/// ```no_run
+/// # use bose_soundtouch::{BoseClient, DeviceState};
+/// # async fn example() -> bose_soundtouch::Result<()> {
+/// let client = BoseClient::new_from_string("192.168.1.143");
+/// let state = DeviceState::load(&client).await?;
+/// println!("Volume: {}", state.volume().borrow().actual);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct DeviceState {
+    info: watch::Sender<DeviceInfo>,
+    now_playing: watch::Sender<NowPlaying>,
+    volume: watch::Sender<Volume>,
+    bass: watch::Sender<Bass>,
+    presets: watch::Sender<Presets>,
+    sources: watch::Sender<Sources>,
+    zone: watch::Sender<Zone>,
+    recents: watch::Sender<Recents>,
+    changes: broadcast::Sender<StateChange>,
+    #[cfg(feature = "websocket")]
+    listener: Option<AbortHandle>,
+}
+
+impl DeviceState {
+    /// Loads the current state of the device over HTTP
+    pub async fn load(client: &BoseClient) -> Result<Self> {
+        Ok(Self {
+            info: watch::Sender::new(client.get_info().await?),
+            now_playing: watch::Sender::new(client.get_status().await?),
+            volume: watch::Sender::new(client.get_volume().await?),
+            bass: watch::Sender::new(client.get_bass().await?),
+            presets: watch::Sender::new(client.get_presets().await?),
+            sources: watch::Sender::new(client.get_sources().await?),
+            zone: watch::Sender::new(client.get_zone().await?),
+            recents: watch::Sender::new(client.get_recents().await?),
+            changes: broadcast::channel(100).0,
+            #[cfg(feature = "websocket")]
+            listener: None,
+        })
+    }
+
+    /// Loads the current state and keeps it in sync with the events of the device
+    ///
+    /// A background task listens on the WebSocket, reconnecting when the
+    /// connection is lost and reloading the state afterwards. The state is
+    /// loaded once the connection is open, so no change is missed in between.
+    /// Bare change notifications are resolved with
+    /// [`BoseClient::enrich_event`]. The task stops when the returned state is
+    /// dropped.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use bose_soundtouch::{BoseClient, DeviceState};
+    /// # async fn example() -> bose_soundtouch::Result<()> {
+    /// let client = BoseClient::new_from_string("192.168.1.143");
+    /// let state = DeviceState::follow(&client).await?;
+    ///
+    /// let mut volume = state.volume();
+    /// while volume.changed().await.is_ok() {
+    ///     println!("Volume: {}", volume.borrow().actual);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if called outside of a Tokio runtime.
+    #[cfg(feature = "websocket")]
+    pub async fn follow(client: &BoseClient) -> Result<Arc<Self>> {
+        let client = client.clone();
+        let mut events = client.events_with_policy(ReconnectPolicy::new().resync(true));
+
+        // Events of the new connection queue up in the stream while loading. If
+        // the first attempt fails, the state is loaded anyway and reloaded once
+        // the connection is established.
+        let mut connected = false;
+        while let Some(event) = events.next().await {
+            match event {
+                SoundTouchEvent::Connected => {
+                    connected = true;
+                    break;
+                }
+                SoundTouchEvent::Reconnecting(_) => break,
+                _ => {}
+            }
+        }
+        let mut state = Self::load(&client).await?;
+
+        Ok(Arc::new_cyclic(|weak: &Weak<Self>| {
+            let weak = weak.clone();
+            let task = tokio::spawn(async move {
+                while let Some(event) = events.next().await {
+                    let reload = !connected && matches!(event, SoundTouchEvent::Connected);
+                    let event = client.enrich_or_keep(event).await;
+                    let Some(state) = weak.upgrade() else { break };
+                    if reload {
+                        connected = state.reload(&client).await.is_ok();
+                    }
+                    state.apply(event);
+                }
+            });
+            state.listener = Some(task.abort_handle());
+            state
+        }))
+    }
+
+    /// Fetches every part of the state again and applies it
+    #[cfg(feature = "websocket")]
+    async fn reload(&self, client: &BoseClient) -> Result<()> {
+        let events = [
+            SoundTouchEvent::InfoUpdated(Some(client.get_info().await?)),
+            SoundTouchEvent::NowPlayingUpdated(NowPlayingUpdate {
+                now_playing: client.get_status().await?,
+            }),
+            SoundTouchEvent::VolumeUpdated(VolumeUpdate {
+                volume: client.get_volume().await?,
+            }),
+            SoundTouchEvent::BassUpdated(Some(client.get_bass().await?)),
+            SoundTouchEvent::PresetsUpdated(client.get_presets().await?),
+            SoundTouchEvent::SourcesUpdated(Some(client.get_sources().await?)),
+            SoundTouchEvent::ZoneUpdated(Some(client.get_zone().await?)),
+            SoundTouchEvent::RecentsUpdated(RecentsUpdate {
+                recents: client.get_recents().await?,
+            }),
+        ];
+        for event in events {
+            self.apply(event);
+        }
+        Ok(())
+    }
+
+    /// Updates the state from an event
+    ///
+    /// Returns whether the event changed any part of the state. Watchers are
+    /// only notified of actual changes. Notifications without payload, like
+    /// [`SoundTouchEvent::VolumeChangeNotified`], are ignored; resolve them
+    /// with [`BoseClient::enrich_event`] first.
+    pub fn apply(&self, event: SoundTouchEvent) -> bool {
+        match event {
+            SoundTouchEvent::InfoUpdated(Some(info)) => replace(&self.info, info).is_some(),
+            SoundTouchEvent::NowPlayingUpdated(update) => {
+                match replace(&self.now_playing, update.now_playing) {
+                    Some(previous) => {
+                        self.publish(StateChange::now_playing(
+                            &previous,
+                            &self.now_playing.borrow(),
+                        ));
+                        true
+                    }
+                    None => false,
+                }
+            }
+            SoundTouchEvent::VolumeUpdated(update) => match replace(&self.volume, update.volume) {
+                Some(previous) => {
+                    self.publish(StateChange::volume(&previous, &self.volume.borrow()));
+                    true
+                }
+                None => false,
+            },
+            SoundTouchEvent::BassUpdated(Some(bass)) => replace(&self.bass, bass).is_some(),
+            SoundTouchEvent::PresetsUpdated(presets) => replace(&self.presets, presets).is_some(),
+            SoundTouchEvent::SourcesUpdated(Some(sources)) => {
+                replace(&self.sources, sources).is_some()
+            }
+            SoundTouchEvent::ZoneUpdated(Some(zone)) => replace(&self.zone, zone).is_some(),
+            SoundTouchEvent::RecentsUpdated(update) => {
+                replace(&self.recents, update.recents).is_some()
+            }
+            _ => false,
+        }
+    }
+
+    fn publish(&self, changes: Vec<StateChange>) {
+        for change in changes {
+            // Sending only fails when nobody is subscribed
+            let _ = self.changes.send(change);
+        }
+    }
+
+    /// Subscribe to the changes detected while applying events
+    pub fn changes(&self) -> broadcast::Receiver<StateChange> {
+        self.changes.subscribe()
+    }
+
+    /// Device information
+    pub fn info(&self) -> watch::Receiver<DeviceInfo> {
+        self.info.subscribe()
+    }
+
+    /// Now playing information
+    pub fn now_playing(&self) -> watch::Receiver<NowPlaying> {
+        self.now_playing.subscribe()
+    }
+
+    /// Volume settings
+    pub fn volume(&self) -> watch::Receiver<Volume> {
+        self.volume.subscribe()
+    }
+
+    /// Bass settings
+    pub fn bass(&self) -> watch::Receiver<Bass> {
+        self.bass.subscribe()
+    }
+
+    /// Presets
+    pub fn presets(&self) -> watch::Receiver<Presets> {
+        self.presets.subscribe()
+    }
+
+    /// Available sources
+    pub fn sources(&self) -> watch::Receiver<Sources> {
+        self.sources.subscribe()
+    }
+
+    /// Multi-room zone
+    pub fn zone(&self) -> watch::Receiver<Zone> {
+        self.zone.subscribe()
+    }
+
+    /// Recently played items
+    pub fn recents(&self) -> watch::Receiver<Recents> {
+        self.recents.subscribe()
+    }
+}
+
+/// Replaces the value of a channel if it differs, returning the previous value
+///
+/// Watchers are only notified when the value changed.
+fn replace<T: PartialEq>(sender: &watch::Sender<T>, value: T) -> Option<T> {
+    let mut previous = None;
+    sender.send_if_modified(|current| {
+        if *current == value {
+            return false;
+        }
+        previous = Some(std::mem::replace(current, value));
+        true
+    });
+    previous
+}
+
+#[cfg(feature = "websocket")]
+impl Drop for DeviceState {
+    fn drop(&mut self) {
+        if let Some(listener) = &self.listener {
+            listener.abort();
+        }
+    }
+}
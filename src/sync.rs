@@ -0,0 +1,121 @@
+//! Synchronization of presets between devices
+
+use crate::{ImportOptions, PresetBackup, Presets, Result, SlotAction, SlotDiff, SoundTouchApi};
+
+#[cfg(feature = "websocket")]
+use {
+    crate::{BoseClient, ReconnectPolicy, SoundTouchEvent},
+    futures_util::StreamExt,
+};
+
+/// Result of synchronizing the presets of one target device
+#[derive(Debug)]
+pub struct SyncOutcome {
+    /// Position of the device in the list of targets
+    pub index: usize,
+    /// Difference of every slot, or the error that stopped the synchronization
+    pub result: Result<Vec<SlotDiff>>,
+}
+
+impl SyncOutcome {
+    /// Slots that were stored or cleared on the device
+    pub fn changed_slots(&self) -> Vec<i32> {
+        match &self.result {
+            Ok(diffs) => diffs
+                .iter()
+                .filter(|diff| {
+                    matches!(
+                        diff.action,
+                        SlotAction::Store | SlotAction::Relabel | SlotAction::Remove
+                    )
+                })
+                .map(|diff| diff.slot)
+                .collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+}
+
+/// Copies the presets of a reference device to other devices
+///
+/// The presets of every target are compared with those of the reference and
+/// only the differing slots are stored or cleared. A slot differs when its
+/// content, name or artwork differs. A failing target does not
+/// stop the others; its error is reported in its [`SyncOutcome`].
+///
+/// # Errors
+/// Returns an error if the presets of the reference device cannot be read.
+///
+/// # Example
+/// ```no_run
+/// # use bose_soundtouch::{sync_presets, BoseClient};
+/// # async fn example() -> bose_soundtouch::Result<()> {
+/// let reference = BoseClient::new_from_string("192.168.1.143");
+/// let targets = [
+///     BoseClient::new_from_string("192.168.1.144"),
+///     BoseClient::new_from_string("192.168.1.145"),
+/// ];
+/// for outcome in sync_presets(&reference, &targets).await? {
+///     match outcome.result {
+///         Ok(_) => println!("{}: changed {:?}", outcome.index, outcome.changed_slots()),
+///         Err(e) => println!("{}: failed: {}", outcome.index, e),
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn sync_presets<R: SoundTouchApi, T: SoundTouchApi>(
+    reference: &R,
+    targets: &[T],
+) -> Result<Vec<SyncOutcome>> {
+    let presets = reference.get_presets().await?;
+    Ok(apply_presets(&presets, targets).await)
+}
+
+/// Makes the presets of every target match the given presets
+async fn apply_presets<T: SoundTouchApi>(presets: &Presets, targets: &[T]) -> Vec<SyncOutcome> {
+    let backup = PresetBackup::new(presets);
+    let options = ImportOptions::new();
+    let mut outcomes = Vec::with_capacity(targets.len());
+    for (index, target) in targets.iter().enumerate() {
+        outcomes.push(SyncOutcome {
+            index,
+            result: target.import_presets(&backup, &options).await,
+        });
+    }
+    outcomes
+}
+
+/// Keeps the presets of other devices in sync with a reference device
+///
+/// Synchronizes once like [`sync_presets`] and then again whenever the
+/// reference device reports changed presets over its WebSocket. The
+/// connection is re-established when it is lost, and the presets are fetched
+/// again after every reconnect. The outcomes of every round are passed to
+/// `on_sync`.
+///
+/// Runs until the listener of the reference device stops; spawn it as a task
+/// and abort the task to stop synchronizing.
+///
+/// # Errors
+/// Returns an error if the presets of the reference device cannot be read
+/// initially.
+#[cfg(feature = "websocket")]
+pub async fn keep_presets_in_sync<T, F>(
+    reference: &BoseClient,
+    targets: &[T],
+    mut on_sync: F,
+) -> Result<()>
+where
+    T: SoundTouchApi,
+    F: FnMut(Vec<SyncOutcome>),
+{
+    let mut events = reference.events_with_policy(ReconnectPolicy::new().resync(true));
+    on_sync(sync_presets(reference, targets).await?);
+    while let Some(event) = events.next().await {
+        if let SoundTouchEvent::PresetsUpdated(presets) = event {
+            on_sync(apply_presets(&presets, targets).await);
+        }
+    }
+    Ok(())
+}
@@ -1,96 +1,168 @@
+use crate::{Bass, DeviceErrors, DeviceInfo, NowPlaying, Preset, Presets, Sources, Volume, Zone};
 use serde::{Deserialize, Serialize};
-
-/// Client for interacting with Bose SoundTouch devices
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct BoseClient {
-    pub hostname: String,
-}
+use std::fmt;
 
 /// Information about the SoundTouch SDK version
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SdkInfo {
     pub server_version: String,
     pub server_build: String,
 }
 
 /// User activity event from the device
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct UserActivity {
     pub device_id: String,
 }
 
-/// Status of artwork for media content
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum ArtStatus {
-    /// No artwork available
-    Invalid,
-    /// Using default artwork
-    ShowDefaultImage,
-    /// Artwork is being downloaded
-    Downloading,
-    /// Artwork is available and loaded
-    ImagePresent,
-}
-
-/// Playback status of the device
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub enum PlayStatus {
-    /// Content is playing
-    #[serde(rename = "PLAY_STATE")]
-    PlayState,
-    /// Playback is paused
-    #[serde(rename = "PAUSE_STATE")]
-    PauseState,
-    /// Playback is stopped
-    #[serde(rename = "STOP_STATE")]
-    StopState,
-    /// Content is buffering
-    #[serde(rename = "BUFFERING_STATE")]
-    BufferingState,
-    /// Invalid play status
-    #[serde(rename = "INVALID_PLAY_STATUS")]
-    Invalid,
-}
-
-/// Volume settings for the device
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Volume {
-    /// Target volume level (0-100)
-    #[serde(rename = "targetvolume")]
-    pub target_volume: u8,
-    /// Current actual volume level (0-100)
-    #[serde(rename = "actualvolume")]
-    pub actual_volume: u8,
-    /// Whether the device is muted
-    #[serde(rename = "muteenabled")]
-    pub mute_enabled: bool,
-}
-
 /// Volume update event from the device
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct VolumeUpdate {
     pub volume: Volume,
 }
 
 /// Source type for media content
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+///
+/// Sources that are not listed here, like `AIRPLAY` on newer firmware, are
+/// kept as [`Source::Other`] with the name reported by the device, so they
+/// can be sent back unchanged.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Source {
-    #[serde(rename = "TUNEIN")]
     Tunein,
-    #[serde(rename = "SPOTIFY")]
     Spotify,
-    #[serde(rename = "AUX")]
     Aux,
-    #[serde(rename = "BLUETOOTH")]
     Bluetooth,
-    #[serde(rename = "INVALID_SOURCE")]
+    InternetRadio,
+    LocalInternetRadio,
+    Pandora,
+    Deezer,
+    Iheart,
+    Amazon,
+    SiriusXm,
+    StoredMusic,
+    LocalMusic,
+    Upnp,
+    Product,
+    Notification,
+    Qplay,
+    Alexa,
     InvalidSource,
-    #[serde(rename = "STANDBY")]
     Standby,
     #[cfg(feature = "unknown-variants")]
-    #[serde(other)]
+    #[deprecated(
+        since = "2.2.0",
+        note = "Unknown sources are reported as Source::Other"
+    )]
     Unknown,
+    /// Source that is not known to this crate, with the name used by the device
+    Other(String),
+}
+
+impl Source {
+    /// Name of the source as used by the device
+    pub fn as_str(&self) -> &str {
+        match self {
+            Source::Tunein => "TUNEIN",
+            Source::Spotify => "SPOTIFY",
+            Source::Aux => "AUX",
+            Source::Bluetooth => "BLUETOOTH",
+            Source::InternetRadio => "INTERNET_RADIO",
+            Source::LocalInternetRadio => "LOCAL_INTERNET_RADIO",
+            Source::Pandora => "PANDORA",
+            Source::Deezer => "DEEZER",
+            Source::Iheart => "IHEART",
+            Source::Amazon => "AMAZON",
+            Source::SiriusXm => "SIRIUSXM",
+            Source::StoredMusic => "STORED_MUSIC",
+            Source::LocalMusic => "LOCAL_MUSIC",
+            Source::Upnp => "UPNP",
+            Source::Product => "PRODUCT",
+            Source::Notification => "NOTIFICATION",
+            Source::Qplay => "QPLAY",
+            Source::Alexa => "ALEXA",
+            Source::InvalidSource => "INVALID_SOURCE",
+            Source::Standby => "STANDBY",
+            #[cfg(feature = "unknown-variants")]
+            Source::Unknown => "UNKNOWN",
+            Source::Other(name) => name,
+        }
+    }
+}
+
+impl From<&str> for Source {
+    fn from(name: &str) -> Self {
+        match name {
+            "TUNEIN" => Source::Tunein,
+            "SPOTIFY" => Source::Spotify,
+            "AUX" => Source::Aux,
+            "BLUETOOTH" => Source::Bluetooth,
+            "INTERNET_RADIO" => Source::InternetRadio,
+            "LOCAL_INTERNET_RADIO" => Source::LocalInternetRadio,
+            "PANDORA" => Source::Pandora,
+            "DEEZER" => Source::Deezer,
+            "IHEART" => Source::Iheart,
+            "AMAZON" => Source::Amazon,
+            "SIRIUSXM" => Source::SiriusXm,
+            "STORED_MUSIC" => Source::StoredMusic,
+            "LOCAL_MUSIC" => Source::LocalMusic,
+            "UPNP" => Source::Upnp,
+            "PRODUCT" => Source::Product,
+            "NOTIFICATION" => Source::Notification,
+            "QPLAY" => Source::Qplay,
+            "ALEXA" => Source::Alexa,
+            "INVALID_SOURCE" => Source::InvalidSource,
+            "STANDBY" => Source::Standby,
+            other => Source::Other(other.to_string()),
+        }
+    }
+}
+
+impl From<String> for Source {
+    fn from(name: String) -> Self {
+        match Source::from(name.as_str()) {
+            Source::Other(_) => Source::Other(name),
+            source => source,
+        }
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for Source {
+    type Err = crate::BoseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Source::from(s))
+    }
+}
+
+impl Serialize for Source {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Source {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Source::from)
+    }
+}
+
+/// Compares with the name used by the device, e.g. `status.source == "STANDBY"`
+impl PartialEq<str> for Source {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for Source {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
 }
 
 /// Network connection states
@@ -128,6 +200,10 @@ pub enum StreamType {
 }
 
 /// Content item types
+#[deprecated(
+    since = "2.2.0",
+    note = "ContentItem keeps the type as a string so it can be sent back to the device"
+)]
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum ContentItemType {
     #[serde(rename = "stationurl")]
@@ -141,116 +217,183 @@ pub enum ContentItemType {
     Unknown,
 }
 
-/// Content item representing a media source or track
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Content item representing a media source, station or track
+///
+/// Used by [`NowPlaying`], [`Preset`] and [`Recent`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename = "ContentItem")]
 pub struct ContentItem {
     /// Source type (e.g., TUNEIN, SPOTIFY, AUX)
     #[serde(rename = "@source")]
     pub source: Source,
-    /// Content type
-    #[serde(rename = "@type", default)]
-    pub item_type: Option<ContentItemType>,
+    /// Content type (e.g., stationurl, tracklisturl)
+    #[serde(rename = "@type", default, skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
     /// Content location/URL
-    #[serde(rename = "@location", default)]
+    #[serde(rename = "@location", default, skip_serializing_if = "Option::is_none")]
     pub location: Option<String>,
     /// Account associated with the source
-    #[serde(rename = "@sourceAccount", default)]
+    #[serde(
+        rename = "@sourceAccount",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
     pub source_account: Option<String>,
     /// Whether this content can be saved as a preset
     #[serde(rename = "@isPresetable", default)]
     pub is_presetable: bool,
     /// Display name of the content
-    #[serde(rename = "itemName")]
-    pub item_name: Option<String>,
+    #[serde(rename = "itemName", default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
     /// URL of container artwork
-    #[serde(rename = "containerArt")]
+    #[serde(
+        rename = "containerArt",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
     pub container_art: Option<String>,
 }
 
-/// Currently playing media information
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct NowPlaying {
-    /// Device ID (MAC address)
-    #[serde(rename = "@deviceID")]
-    pub device_id: String,
-    /// Current source
-    #[serde(rename = "@source")]
-    pub source: Source,
-    /// Account associated with the source
-    #[serde(rename = "@sourceAccount", default)]
-    pub source_account: Option<String>,
-    /// Content item details
-    #[serde(rename = "ContentItem")]
-    pub content_item: ContentItem,
-    /// Track name
-    pub track: Option<String>,
-    /// Artist name
-    pub artist: Option<String>,
-    /// Album name
-    pub album: Option<String>,
-    /// Station name (for radio)
-    #[serde(rename = "stationName")]
-    pub station_name: Option<String>,
-    /// Artwork URL
-    pub art: Option<String>,
-    /// Status of the artwork
-    #[serde(rename = "artImageStatus")]
-    pub art_status: Option<ArtStatus>,
-    /// Current playback status
-    #[serde(rename = "playStatus")]
-    pub play_status: PlayStatus,
-    /// Type of stream
-    #[serde(rename = "streamType")]
-    pub stream_type: Option<StreamType>,
-    /// Whether content can be favorited
-    #[serde(rename = "favoriteEnabled")]
-    pub favorite_enabled: Option<String>,
-    /// Description of the content
-    pub description: Option<String>,
-    /// Station location (for radio)
-    #[serde(rename = "stationLocation")]
-    pub station_location: Option<String>,
+impl ContentItem {
+    /// Content type, for code written against the former `item_type` field
+    ///
+    /// Returns `None` for types the enum does not know.
+    #[deprecated(since = "2.2.0", note = "Use the `content_type` field instead")]
+    pub fn item_type(&self) -> Option<ContentItemType> {
+        match self.content_type.as_deref()? {
+            "stationurl" => Some(ContentItemType::StationUrl),
+            "tracklisturl" => Some(ContentItemType::TracklistUrl),
+            "DO_NOT_RESUME" => Some(ContentItemType::DoNotResume),
+            #[cfg(feature = "unknown-variants")]
+            _ => Some(ContentItemType::Unknown),
+            #[cfg(not(feature = "unknown-variants"))]
+            _ => None,
+        }
+    }
+
+    /// Display name, for code written against the former `item_name` field
+    #[deprecated(since = "2.2.0", note = "Use the `name` field instead")]
+    pub fn item_name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
 }
 
 /// Now playing update event from the device
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct NowPlayingUpdate {
     #[serde(rename = "nowPlaying")]
     pub now_playing: NowPlaying,
 }
 
-/// Preset station/source configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Preset {
-    pub id: u8,
-    #[serde(rename = "ContentItem")]
-    pub content_item: ContentItem,
-}
-
 /// Recently played item
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Recent {
+    /// Device that played the item
+    #[serde(rename = "@deviceID")]
     pub device_id: String,
+    /// Unix timestamp of when the item was played
+    #[serde(rename = "@utcTime")]
     pub utc_time: u64,
-    pub id: String,
+    /// Identifier of the recent entry, if the firmware reports one
+    #[serde(rename = "@id", default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// The played content
     #[serde(rename = "contentItem")]
     pub content_item: ContentItem,
 }
 
-/// Collection of recently played items
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Collection of recently played items, most recent first
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename = "recents")]
 pub struct Recents {
+    #[serde(default)]
     pub recent: Vec<Recent>,
 }
 
 /// Recents update event from the device
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct RecentsUpdate {
     pub recents: Recents,
 }
 
-/// Network connection state information
+/// Volume notification as sent by the device
+///
+/// The firmware sends either the new volume or a bare `<volumeUpdated/>`
+/// signal, e.g. on zone slaves.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VolumeNotification {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub volume: Option<Volume>,
+}
+
+/// Now playing notification as sent by the device
+///
+/// The firmware sends either the new state or a bare `<nowPlayingUpdated/>`
+/// signal, e.g. on zone slaves.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NowPlayingNotification {
+    #[serde(
+        rename = "nowPlaying",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub now_playing: Option<NowPlaying>,
+}
+
+/// Presets update event from the device
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PresetsUpdate {
+    pub presets: Presets,
+}
+
+/// Now selection update event, sent when a preset is selected
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NowSelectionUpdate {
+    pub preset: Preset,
+}
+
+/// Bass update event from the device
+///
+/// The firmware usually sends this as a bare `<bassUpdated/>` signal.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BassUpdate {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bass: Option<Bass>,
+}
+
+/// Zone update event from the device
+///
+/// The firmware usually sends this as a bare `<zoneUpdated/>` signal.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ZoneUpdate {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub zone: Option<Zone>,
+}
+
+/// Sources update event from the device
+///
+/// The firmware usually sends this as a bare `<sourcesUpdated/>` signal.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SourcesUpdate {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sources: Option<Sources>,
+}
+
+/// Device info update event, e.g. after the device was renamed
+///
+/// The firmware usually sends this as a bare `<infoUpdated/>` signal.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InfoUpdate {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub info: Option<DeviceInfo>,
+}
+
+/// Update that signals a change without carrying any data
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EmptyUpdate {}
+
+/// Network connection state information
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ConnectionState {
     /// Connection state
     #[serde(rename = "@state")]
@@ -265,7 +408,7 @@ pub struct ConnectionState {
 
 /// Events that can be received from the device's WebSocket API
 #[allow(clippy::large_enum_variant)]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub enum SoundTouchEvent {
     /// Device SDK information received
     DeviceInfo(SdkInfo),
@@ -275,48 +418,132 @@ pub enum SoundTouchEvent {
     VolumeUpdated(VolumeUpdate),
     /// Now playing information changed
     NowPlayingUpdated(NowPlayingUpdate),
+    /// Volume changed, but the device did not send the new settings
+    VolumeChangeNotified,
+    /// Now playing information changed, but the device did not send the new state
+    NowPlayingChangeNotified,
     /// Preset was selected
     PresetSelected(Preset),
     /// Recently played items updated
     RecentsUpdated(RecentsUpdate),
     /// Network connection state changed
     ConnectionStateUpdated(ConnectionState),
+    /// Presets were added, cleared or modified
+    PresetsUpdated(Presets),
+    /// Association with a cloud account changed
+    AccountModeUpdated,
+    /// Device reported an error
+    ErrorNotification(DeviceErrors),
+    /// Bass settings changed, with the new settings if the device sent them
+    BassUpdated(Option<Bass>),
+    /// Zone configuration changed, with the new zone if the device sent it
+    ZoneUpdated(Option<Zone>),
+    /// Software update status changed
+    SoftwareUpdateStatusUpdated,
+    /// Site survey results changed
+    SiteSurveyResultsUpdated,
+    /// Available sources changed, with the new list if the device sent it
+    SourcesUpdated(Option<Sources>),
+    /// Device info changed, with the new info if the device sent it
+    InfoUpdated(Option<DeviceInfo>),
+    /// Connecting to the WebSocket for the first time
+    Connecting,
+    /// WebSocket connection established
+    Connected,
+    /// Reconnecting after the connection was lost, with the 1-based attempt number
+    Reconnecting(u32),
     /// WebSocket connection closed
     Disconnected,
 }
 
 /// Collection of updates received from the device
-#[derive(Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Updates {
     #[serde(rename = "@deviceID")]
     pub device_id: String,
+    /// Updates in the order they appear in the message
+    #[serde(rename = "$value", default)]
+    pub updates: Vec<Update>,
+}
+
+/// A single notification carried by an [`Updates`] message
+#[derive(Debug, Serialize, Deserialize)]
+pub enum Update {
     #[serde(rename = "volumeUpdated")]
-    pub volume_updated: Option<VolumeUpdate>,
+    Volume(VolumeNotification),
     #[serde(rename = "nowPlayingUpdated")]
-    pub now_playing_updated: Option<NowPlayingUpdate>,
+    NowPlaying(NowPlayingNotification),
     #[serde(rename = "recentsUpdated")]
-    pub recents_updated: Option<RecentsUpdate>,
+    Recents(RecentsUpdate),
     #[serde(rename = "connectionStateUpdated")]
-    pub connection_state_updated: Option<ConnectionState>,
+    ConnectionState(ConnectionState),
+    #[serde(rename = "presetsUpdated")]
+    Presets(PresetsUpdate),
+    #[serde(rename = "acctModeUpdated")]
+    AcctMode(EmptyUpdate),
+    #[serde(rename = "errorNotification")]
+    Error(DeviceErrors),
+    #[serde(rename = "bassUpdated")]
+    Bass(BassUpdate),
+    #[serde(rename = "zoneUpdated")]
+    Zone(ZoneUpdate),
+    #[serde(rename = "swUpdateStatusUpdated")]
+    SwUpdateStatus(EmptyUpdate),
+    #[serde(rename = "siteSurveyResultsUpdated")]
+    SiteSurveyResults(EmptyUpdate),
+    #[serde(rename = "sourcesUpdated")]
+    Sources(SourcesUpdate),
+    #[serde(rename = "nowSelectionUpdated")]
+    NowSelection(NowSelectionUpdate),
+    #[serde(rename = "infoUpdated")]
+    Info(InfoUpdate),
+    /// Notification that is not known to this crate
+    #[serde(other)]
+    Unknown,
 }
 
-impl std::fmt::Debug for Updates {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        writeln!(f, "Updates {{")?;
-        writeln!(f, "  device_id: {}", self.device_id)?;
-        if let Some(ref v) = self.volume_updated {
-            writeln!(f, "  volume_updated: {:?}", v)?;
-        }
-        if let Some(ref n) = self.now_playing_updated {
-            writeln!(f, "  now_playing_updated: {:?}", n)?;
-        }
-        if let Some(ref r) = self.recents_updated {
-            writeln!(f, "  recents_updated: {:?}", r)?;
-        }
-        if let Some(ref c) = self.connection_state_updated {
-            writeln!(f, "  connection_state_updated: {:?}", c)?;
-        }
-        write!(f, "}}")
+#[cfg(feature = "websocket")]
+impl Update {
+    /// Converts the update into an event, if it is known
+    pub(crate) fn into_event(self) -> Option<SoundTouchEvent> {
+        Some(match self {
+            Update::Volume(update) => match update.volume {
+                Some(volume) => SoundTouchEvent::VolumeUpdated(VolumeUpdate { volume }),
+                None => SoundTouchEvent::VolumeChangeNotified,
+            },
+            Update::NowPlaying(update) => match update.now_playing {
+                Some(now_playing) => {
+                    SoundTouchEvent::NowPlayingUpdated(NowPlayingUpdate { now_playing })
+                }
+                None => SoundTouchEvent::NowPlayingChangeNotified,
+            },
+            Update::Recents(recents) => SoundTouchEvent::RecentsUpdated(recents),
+            Update::ConnectionState(connection) => {
+                SoundTouchEvent::ConnectionStateUpdated(connection)
+            }
+            Update::Presets(update) => SoundTouchEvent::PresetsUpdated(update.presets),
+            Update::AcctMode(_) => SoundTouchEvent::AccountModeUpdated,
+            Update::Error(errors) => SoundTouchEvent::ErrorNotification(errors),
+            Update::Bass(update) => SoundTouchEvent::BassUpdated(update.bass),
+            Update::Zone(update) => SoundTouchEvent::ZoneUpdated(update.zone),
+            Update::SwUpdateStatus(_) => SoundTouchEvent::SoftwareUpdateStatusUpdated,
+            Update::SiteSurveyResults(_) => SoundTouchEvent::SiteSurveyResultsUpdated,
+            Update::Sources(update) => SoundTouchEvent::SourcesUpdated(update.sources),
+            Update::NowSelection(update) => SoundTouchEvent::PresetSelected(update.preset),
+            Update::Info(update) => SoundTouchEvent::InfoUpdated(update.info),
+            Update::Unknown => return None,
+        })
+    }
+}
+
+#[cfg(feature = "websocket")]
+impl Updates {
+    /// Converts every known update into an event, in document order
+    pub(crate) fn into_events(self) -> Vec<SoundTouchEvent> {
+        self.updates
+            .into_iter()
+            .filter_map(Update::into_event)
+            .collect()
     }
 }
 
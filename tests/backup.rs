@@ -0,0 +1,247 @@
+use bose_soundtouch::*;
+
+fn sample_presets() -> Presets {
+    quick_xml::de::from_str(
+        r#"<presets>
+            <preset id="1" createdOn="1701220500" updatedOn="1701220500">
+                <ContentItem source="TUNEIN" type="stationurl" location="/v1/playback/station/s17077" sourceAccount="" isPresetable="true">
+                    <itemName>Qmusic België</itemName>
+                    <containerArt>http://cdn-profiles.tunein.com/s17077/images/logoq.png</containerArt>
+                </ContentItem>
+            </preset>
+            <preset id="2">
+                <ContentItem source="AUX" sourceAccount="AUX" isPresetable="true">
+                    <itemName>AUX IN</itemName>
+                </ContentItem>
+            </preset>
+        </presets>"#,
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_backup_keeps_content_items() {
+    let presets = sample_presets();
+    let backup = PresetBackup::new(&presets);
+    assert_eq!(backup.version, PRESET_BACKUP_VERSION);
+    assert_eq!(backup.presets.len(), 2);
+
+    let entry = backup.entry(1).unwrap();
+    assert_eq!(entry.content_type.as_deref(), Some("stationurl"));
+    assert_eq!(
+        entry.art.as_deref(),
+        Some("http://cdn-profiles.tunein.com/s17077/images/logoq.png")
+    );
+    assert_eq!(entry.content_item(), presets.items[0].content_item);
+    assert_eq!(
+        backup.entry(2).unwrap().content_item(),
+        presets.items[1].content_item
+    );
+}
+
+#[test]
+fn test_backup_validation() {
+    let mut backup = PresetBackup::new(&sample_presets());
+    assert!(backup.validate().is_ok());
+
+    backup.version = PRESET_BACKUP_VERSION + 1;
+    assert!(matches!(
+        backup.validate(),
+        Err(BoseError::UnsupportedBackupVersion(v)) if v == PRESET_BACKUP_VERSION + 1
+    ));
+
+    backup.version = PRESET_BACKUP_VERSION;
+    backup.presets[1].slot = 1;
+    assert!(matches!(
+        backup.validate(),
+        Err(BoseError::InvalidPreset(_))
+    ));
+    backup.presets[1].slot = 7;
+    assert!(matches!(
+        backup.validate(),
+        Err(BoseError::InvalidPreset(_))
+    ));
+}
+
+#[test]
+fn test_diff_presets_follows_slot_policies() {
+    let current = sample_presets();
+    let mut backup = PresetBackup::new(&current);
+    // Slot 1 is unchanged, slot 2 is missing and slot 3 is new
+    backup.presets[1].slot = 3;
+
+    let actions = |options: &ImportOptions| -> Vec<SlotAction> {
+        diff_presets(&current, &backup, options)
+            .iter()
+            .map(|diff| diff.action)
+            .collect()
+    };
+    use SlotAction::*;
+    assert_eq!(
+        actions(&ImportOptions::new()),
+        vec![Unchanged, Remove, Store, Unchanged, Unchanged, Unchanged]
+    );
+    assert_eq!(
+        actions(&ImportOptions::new().policy(SlotPolicy::Skip)),
+        vec![Unchanged, Skip, Store, Unchanged, Unchanged, Unchanged]
+    );
+    assert_eq!(
+        actions(
+            &ImportOptions::new()
+                .policy(SlotPolicy::Skip)
+                .slot_policy(2, SlotPolicy::Overwrite)
+        ),
+        vec![Unchanged, Remove, Store, Unchanged, Unchanged, Unchanged]
+    );
+
+    let diff = &diff_presets(&current, &backup, &ImportOptions::new())[2];
+    assert_eq!(diff.slot, 3);
+    assert!(diff.current.is_none());
+    assert_eq!(diff.wanted.as_ref().unwrap().source, Source::Aux);
+}
+
+#[test]
+fn test_diff_presets_reports_name_and_art() {
+    let current = sample_presets();
+    let mut backup = PresetBackup::new(&current);
+    backup.presets[0].name = Some("Qmusic".to_string());
+    backup.presets[0].art = None;
+    assert!(backup.presets[0].same_content(&PresetBackup::new(&current).presets[0]));
+
+    let diff = diff_presets(&current, &backup, &ImportOptions::new());
+    assert_eq!(diff[0].action, SlotAction::Relabel);
+    let diff = diff_presets(
+        &current,
+        &backup,
+        &ImportOptions::new().policy(SlotPolicy::Skip),
+    );
+    assert_eq!(diff[0].action, SlotAction::Skip);
+
+    backup.presets[0].location = Some("/v1/playback/station/s67006".to_string());
+    let diff = diff_presets(&current, &backup, &ImportOptions::new());
+    assert_eq!(diff[0].action, SlotAction::Store);
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_backup_json_round_trip() {
+    let backup = PresetBackup::from_json(include_str!("samples/presets_backup.json")).unwrap();
+    assert_eq!(backup.device_name.as_deref(), Some("Living Room"));
+    let radio = backup.entry(4).unwrap();
+    assert_eq!(radio.source, Source::InternetRadio);
+    assert!(radio.is_presetable);
+    assert!(radio.content_type.is_none());
+
+    let json = backup.to_json().unwrap();
+    assert_eq!(PresetBackup::from_json(&json).unwrap(), backup);
+
+    let newer = json.replacen("\"version\": 1", "\"version\": 2", 1);
+    assert!(matches!(
+        PresetBackup::from_json(&newer),
+        Err(BoseError::UnsupportedBackupVersion(2))
+    ));
+}
+
+#[cfg(feature = "fake")]
+#[tokio::test]
+async fn test_import_presets() {
+    let reference = FakeSoundTouch::with_state(FakeState {
+        presets: sample_presets(),
+        ..FakeState::default()
+    });
+    let backup = reference.export_presets().await.unwrap();
+    assert_eq!(backup.device_id.as_deref(), Some("000C8AB02519"));
+
+    let target = FakeSoundTouch::new();
+    let dry_run = ImportOptions::new().dry_run(true);
+    let diffs = target.import_presets(&backup, &dry_run).await.unwrap();
+    assert_eq!(diffs[0].action, SlotAction::Store);
+    assert!(target.state().presets.items.is_empty());
+
+    target
+        .import_presets(&backup, &ImportOptions::new())
+        .await
+        .unwrap();
+    assert_eq!(
+        PresetBackup::new(&target.state().presets).presets,
+        backup.presets
+    );
+
+    // Importing again changes nothing
+    let diffs = target
+        .import_presets(&backup, &ImportOptions::new())
+        .await
+        .unwrap();
+    assert!(diffs
+        .iter()
+        .all(|diff| diff.action == SlotAction::Unchanged));
+
+    // A new name for the same station is stored as well
+    let mut renamed = backup.clone();
+    renamed.presets[0].name = Some("Qmusic".to_string());
+    let diffs = target
+        .import_presets(&renamed, &ImportOptions::new())
+        .await
+        .unwrap();
+    assert_eq!(diffs[0].action, SlotAction::Relabel);
+    assert_eq!(
+        target.state().presets.items[0].content_item.name.as_deref(),
+        Some("Qmusic")
+    );
+}
+
+#[cfg(feature = "fake")]
+#[tokio::test]
+async fn test_sync_presets_reports_per_device() {
+    let reference = FakeSoundTouch::with_state(FakeState {
+        presets: sample_presets(),
+        ..FakeState::default()
+    });
+    let in_sync = FakeSoundTouch::with_state(FakeState {
+        presets: sample_presets(),
+        ..FakeState::default()
+    });
+    let empty = FakeSoundTouch::new();
+    let failing = FakeSoundTouch::new();
+    failing.fail_next(BoseError::InvalidPreset("boom".to_string()));
+    let targets = [in_sync, empty, failing];
+
+    let outcomes = sync_presets(&reference, &targets).await.unwrap();
+    assert_eq!(outcomes.len(), 3);
+    assert!(outcomes[0].changed_slots().is_empty());
+    assert_eq!(outcomes[1].changed_slots(), vec![1, 2]);
+    assert!(outcomes[2].result.is_err());
+
+    // Only the differing slots were written
+    assert!(!targets[0]
+        .calls()
+        .iter()
+        .any(|call| matches!(call, Call::StorePreset { .. })));
+    assert_eq!(
+        PresetBackup::new(&targets[1].state().presets).presets,
+        PresetBackup::new(&reference.state().presets).presets
+    );
+}
+
+#[cfg(feature = "fake")]
+#[tokio::test]
+async fn test_sync_presets_copies_renamed_presets() {
+    let reference = FakeSoundTouch::with_state(FakeState {
+        presets: sample_presets(),
+        ..FakeState::default()
+    });
+    let targets = [FakeSoundTouch::with_state(FakeState {
+        presets: sample_presets(),
+        ..FakeState::default()
+    })];
+    reference.update_state(|state| {
+        state.presets.items[0].content_item.name = Some("Qmusic".to_string());
+    });
+
+    let outcomes = sync_presets(&reference, &targets).await.unwrap();
+    assert_eq!(outcomes[0].changed_slots(), vec![1]);
+    assert_eq!(
+        targets[0].state().presets.items[0].content_item,
+        reference.state().presets.items[0].content_item
+    );
+}
@@ -0,0 +1,735 @@
+#[cfg(feature = "emulator")]
+mod tests {
+    use bose_soundtouch::*;
+    use futures_util::StreamExt;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tokio::time::timeout;
+
+    async fn start() -> Emulator {
+        Emulator::start(FakeState::default()).await.unwrap()
+    }
+
+    /// Waits until a WebSocket client is connected to the emulator
+    async fn wait_for_ws_client(emulator: &Emulator) {
+        wait_for_ws_clients(emulator, |clients| clients > 0).await
+    }
+
+    /// Waits until every WebSocket client has disconnected from the emulator
+    async fn wait_for_ws_disconnect(emulator: &Emulator) {
+        wait_for_ws_clients(emulator, |clients| clients == 0).await
+    }
+
+    async fn wait_for_ws_clients(emulator: &Emulator, done: impl Fn(usize) -> bool) {
+        timeout(Duration::from_secs(5), async {
+            while !done(emulator.websocket_clients()) {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+    }
+
+    /// The `CLIENT_XML_ERROR` reply from section 5 of the API documentation
+    fn client_xml_error() -> BoseError {
+        BoseError::DeviceError(quick_xml::de::from_str(include_str!("samples/errors.xml")).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_emulator_serves_http_api() {
+        let emulator = start().await;
+        let client = emulator.client().unwrap();
+
+        assert_eq!(client.get_info().await.unwrap().device_id, "000C8AB02519");
+        assert_eq!(client.get_status().await.unwrap().source, "STANDBY");
+
+        client.power().await.unwrap();
+        client.set_volume(35).await.unwrap();
+        client.set_bass(-4).await.unwrap();
+        client.set_name("Kitchen").await.unwrap();
+        client.select_source("BLUETOOTH", None).await.unwrap();
+
+        assert_eq!(client.get_status().await.unwrap().source, "BLUETOOTH");
+        assert_eq!(client.get_volume().await.unwrap().actual, 35);
+        assert_eq!(client.get_bass().await.unwrap().actual, -4);
+        assert_eq!(client.get_info().await.unwrap().name, "Kitchen");
+        assert_eq!(client.get_bass_capabilities().await.unwrap().min_value, -9);
+        assert!(client.is_source_available("AUX").await.unwrap());
+
+        let calls = emulator.calls();
+        assert!(calls.contains(&Call::Key(KeyValue::Power)));
+        assert!(calls.contains(&Call::SelectSource {
+            source: "BLUETOOTH".to_string(),
+            source_account: None,
+        }));
+    }
+
+    #[tokio::test]
+    async fn test_emulator_manages_zone() {
+        let emulator = start().await;
+        let client = emulator.client().unwrap();
+        assert!(!client.is_in_zone().await.unwrap());
+
+        let slaves = vec![("192.168.1.144".to_string(), "001122334455".to_string())];
+        client.set_zone(&slaves).await.unwrap();
+        client
+            .add_zone_slave("192.168.1.145", "001122334466")
+            .await
+            .unwrap();
+        assert_eq!(client.get_zone().await.unwrap().members.len(), 3);
+
+        client
+            .remove_zone_slave("192.168.1.144", "001122334455")
+            .await
+            .unwrap();
+        let zone = client.get_zone().await.unwrap();
+        assert_eq!(zone.master, "000C8AB02519");
+        assert_eq!(zone.members.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_emulator_replays_recents() {
+        let state = FakeState {
+            recents: quick_xml::de::from_str(include_str!("samples/recents.xml")).unwrap(),
+            ..FakeState::default()
+        };
+        let emulator = Emulator::start(state).await.unwrap();
+        let client = emulator.client().unwrap();
+
+        let recents = client.get_recents().await.unwrap();
+        assert_eq!(recents, emulator.state().recents);
+
+        client.play_recent(&recents.recent[1]).await.unwrap();
+        assert_eq!(client.get_status().await.unwrap().source, Source::Bluetooth);
+    }
+
+    #[tokio::test]
+    async fn test_emulator_manages_presets() {
+        let emulator = start().await;
+        let client = emulator.client().unwrap();
+        let radio = ContentItem {
+            source: Source::Tunein,
+            content_type: Some("stationurl".to_string()),
+            location: Some("/v1/playback/station/s17077".to_string()),
+            source_account: Some(String::new()),
+            is_presetable: true,
+            name: Some("Qmusic België".to_string()),
+            container_art: None,
+        };
+
+        let presets = client.store_preset(2, &radio).await.unwrap();
+        assert_eq!(presets.items.len(), 1);
+        assert_eq!(presets.items[0].id, 2);
+        assert_eq!(presets.items[0].content_item.location, radio.location);
+        assert_eq!(presets, client.get_presets().await.unwrap());
+
+        // Swapping with an empty slot moves the preset
+        let presets = client.swap_presets(2, 5).await.unwrap();
+        assert_eq!(presets.items.len(), 1);
+        assert_eq!(presets.items[0].id, 5);
+
+        let presets = client.remove_preset(5).await.unwrap();
+        assert!(presets.items.is_empty());
+
+        assert!(matches!(
+            client.store_preset(7, &radio).await,
+            Err(BoseError::InvalidPreset(_))
+        ));
+        assert!(matches!(
+            client.remove_preset(0).await,
+            Err(BoseError::InvalidPreset(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_emulator_toggles_playback() {
+        let emulator = start().await;
+        let client = emulator.client().unwrap();
+        client.power().await.unwrap();
+
+        let now_playing = client
+            .toggle_playback_and_wait(Duration::from_secs(5))
+            .await
+            .unwrap();
+        assert_eq!(now_playing.play_status, Some(PlayStatus::PauseState));
+        assert_eq!(
+            client.toggle_playback().await.unwrap(),
+            PlayStatus::PlayState
+        );
+
+        client.play_pause().await.unwrap();
+        assert_eq!(
+            client.get_status().await.unwrap().play_status,
+            Some(PlayStatus::PauseState)
+        );
+        assert!(emulator.calls().contains(&Call::Key(KeyValue::PlayPause)));
+    }
+
+    #[tokio::test]
+    async fn test_emulator_volume_steps_and_mute() {
+        let emulator = start().await;
+        let mut client = emulator.client().unwrap();
+
+        assert!(matches!(
+            client.set_volume(250).await,
+            Err(BoseError::InvalidVolume(250))
+        ));
+        assert_eq!(client.volume_up(5).await.unwrap(), VolumeLevel::new(25));
+        assert_eq!(client.volume_down(30).await.unwrap(), VolumeLevel::MIN);
+        assert_eq!(client.get_volume().await.unwrap().actual, 0);
+
+        client.set_max_volume(Some(VolumeLevel::new(40)));
+        client.set_volume(80).await.unwrap();
+        assert_eq!(client.get_volume().await.unwrap().actual, 40);
+        assert_eq!(client.volume_up(10).await.unwrap(), VolumeLevel::new(40));
+
+        client.set_mute(true).await.unwrap();
+        client.set_mute(true).await.unwrap();
+        assert_eq!(client.get_volume().await.unwrap().mute_enabled, Some(true));
+        client.set_mute(false).await.unwrap();
+        assert_eq!(client.get_volume().await.unwrap().mute_enabled, Some(false));
+        let mutes = emulator
+            .calls()
+            .iter()
+            .filter(|call| **call == Call::Key(KeyValue::Mute))
+            .count();
+        assert_eq!(mutes, 2);
+    }
+
+    #[tokio::test]
+    async fn test_emulator_volume_through_trait() {
+        async fn quiet<S: SoundTouchApi>(speaker: &S) -> Result<VolumeLevel> {
+            speaker.set_mute(true).await?;
+            speaker.volume_down(5).await
+        }
+
+        let emulator = start().await;
+        let mut client = emulator.client().unwrap();
+        client.set_max_volume(Some(VolumeLevel::new(30)));
+
+        assert_eq!(quiet(&client).await.unwrap(), VolumeLevel::new(15));
+        assert_eq!(
+            SoundTouchApi::volume_up(&client, 50).await.unwrap(),
+            VolumeLevel::new(30)
+        );
+        // The client checks the mute state before toggling
+        assert_eq!(
+            emulator.calls()[..2],
+            [Call::GetVolume, Call::Key(KeyValue::Mute)]
+        );
+
+        let session = client.connect_session().await.unwrap();
+        session.set_volume(90).await.unwrap();
+        assert_eq!(session.get_volume().await.unwrap().actual, 30);
+    }
+
+    #[tokio::test]
+    async fn test_emulator_handles_held_keys() {
+        let emulator = start().await;
+        let client = emulator.client().unwrap();
+
+        client
+            .hold_key(&KeyValue::VolumeUp, Duration::from_millis(50))
+            .await
+            .unwrap();
+        assert_eq!(client.get_volume().await.unwrap().actual, 21);
+
+        client.press_key(&KeyValue::VolumeDown).await.unwrap();
+        client.release_key(&KeyValue::VolumeDown).await.unwrap();
+        assert_eq!(client.get_volume().await.unwrap().actual, 20);
+        assert_eq!(
+            emulator
+                .calls()
+                .iter()
+                .filter(|call| matches!(call, Call::Key(_)))
+                .count(),
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn test_emulator_sets_shuffle_and_repeat() {
+        let emulator = start().await;
+        let client = emulator.client().unwrap();
+        client.power().await.unwrap();
+
+        client.set_shuffle(true).await.unwrap();
+        client.set_repeat(RepeatMode::One).await.unwrap();
+        let status = client.get_status().await.unwrap();
+        assert_eq!(status.shuffle_setting, Some(ShuffleMode::On));
+        assert_eq!(status.repeat_setting, Some(RepeatMode::One));
+
+        client.set_shuffle(false).await.unwrap();
+        assert_eq!(
+            client.get_status().await.unwrap().shuffle_setting,
+            Some(ShuffleMode::Off)
+        );
+        assert!(emulator.calls().contains(&Call::Key(KeyValue::RepeatOne)));
+    }
+
+    #[tokio::test]
+    async fn test_emulator_reports_failures() {
+        let emulator = start().await;
+        let client = emulator.client().unwrap();
+
+        emulator.fail_next(client_xml_error());
+        let error = client.set_volume(10).await.unwrap_err();
+        assert!(matches!(error, BoseError::DeviceError(ref e) if e.errors[0].value == 1019));
+
+        // Other failures are answered with HTTP 500, which GET requests retry
+        emulator.fail_next(BoseError::InvalidPreset("boom".to_string()));
+        assert_eq!(client.get_volume().await.unwrap().actual, 20);
+    }
+
+    #[tokio::test]
+    async fn test_emulator_pushes_updates() {
+        let emulator = start().await;
+        let mut client = emulator.client().unwrap();
+        let mut events = client.subscribe();
+        let listener = emulator.client().unwrap();
+        tokio::spawn(async move { client.connect_and_listen().await });
+
+        wait_for_ws_client(&emulator).await;
+
+        listener.set_volume(30).await.unwrap();
+        let event = timeout(Duration::from_secs(5), events.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        match event {
+            SoundTouchEvent::VolumeUpdated(update) => assert_eq!(update.volume.actual, 30),
+            other => panic!("unexpected event: {:?}", other),
+        }
+
+        emulator.update_state(|state| {
+            state.now_playing.source = Source::Aux;
+            state.now_playing.content_item.source = Source::Aux;
+            state.now_playing.play_status = Some(PlayStatus::PauseState);
+        });
+        let event = timeout(Duration::from_secs(5), events.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        match event {
+            SoundTouchEvent::NowPlayingUpdated(update) => {
+                assert_eq!(update.now_playing.source, Source::Aux);
+                // HTTP and WebSocket produce the same model
+                assert_eq!(update.now_playing, listener.get_status().await.unwrap());
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_emulator_enriches_bare_notifications() {
+        let emulator = start().await;
+        let client = emulator.client().unwrap();
+        client.set_volume(42).await.unwrap();
+
+        match client
+            .enrich_event(SoundTouchEvent::VolumeChangeNotified)
+            .await
+            .unwrap()
+        {
+            SoundTouchEvent::VolumeUpdated(update) => assert_eq!(update.volume.actual, 42),
+            other => panic!("unexpected event: {:?}", other),
+        }
+        match client
+            .enrich_event(SoundTouchEvent::NowPlayingChangeNotified)
+            .await
+            .unwrap()
+        {
+            SoundTouchEvent::NowPlayingUpdated(update) => {
+                assert_eq!(update.now_playing.source, Source::Standby)
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+        assert!(matches!(
+            client
+                .enrich_event(SoundTouchEvent::AccountModeUpdated)
+                .await
+                .unwrap(),
+            SoundTouchEvent::AccountModeUpdated
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_emulator_pushes_enriched_updates() {
+        let emulator = start().await;
+        let mut client = emulator.client().unwrap();
+        client.set_enrich_events(true);
+        let mut events = client.subscribe();
+        let controller = emulator.client().unwrap();
+        tokio::spawn(async move { client.connect_and_listen().await });
+
+        wait_for_ws_client(&emulator).await;
+
+        // The emulator signals bass changes with a bare <bassUpdated />
+        controller.set_bass(-3).await.unwrap();
+        let event = timeout(Duration::from_secs(5), events.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        match event {
+            SoundTouchEvent::BassUpdated(Some(bass)) => assert_eq!(bass.actual, -3),
+            other => panic!("unexpected event: {:?}", other),
+        }
+
+        controller.set_name("Kitchen").await.unwrap();
+        let event = timeout(Duration::from_secs(5), events.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        match event {
+            SoundTouchEvent::InfoUpdated(Some(info)) => assert_eq!(info.name, "Kitchen"),
+            other => panic!("unexpected event: {:?}", other),
+        }
+    }
+
+    /// Receives the next event that is not the SDK greeting
+    async fn next_event(
+        events: &mut tokio::sync::broadcast::Receiver<SoundTouchEvent>,
+    ) -> SoundTouchEvent {
+        timeout(Duration::from_secs(5), async {
+            loop {
+                match events.recv().await.unwrap() {
+                    SoundTouchEvent::DeviceInfo(_) => continue,
+                    event => return event,
+                }
+            }
+        })
+        .await
+        .unwrap()
+    }
+
+    fn fast_reconnect() -> ReconnectPolicy {
+        ReconnectPolicy::new()
+            .initial_backoff(Duration::from_millis(10))
+            .max_backoff(Duration::from_millis(50))
+            .jitter(false)
+    }
+
+    #[tokio::test]
+    async fn test_listener_reconnects_and_resyncs() {
+        let emulator = start().await;
+        let mut client = emulator.client().unwrap();
+        let mut events = client.subscribe();
+        let controller = emulator.client().unwrap();
+        tokio::spawn(async move {
+            client
+                .listen_with_reconnect(
+                    fast_reconnect()
+                        .initial_backoff(Duration::from_millis(200))
+                        .resync(true),
+                )
+                .await
+        });
+
+        assert!(matches!(
+            next_event(&mut events).await,
+            SoundTouchEvent::Connecting
+        ));
+        assert!(matches!(
+            next_event(&mut events).await,
+            SoundTouchEvent::Connected
+        ));
+
+        // Simulate a reboot, during which the volume changes before the
+        // listener reconnects
+        emulator.disconnect_websockets();
+        assert!(matches!(
+            next_event(&mut events).await,
+            SoundTouchEvent::Disconnected
+        ));
+        controller.set_volume(33).await.unwrap();
+        assert!(matches!(
+            next_event(&mut events).await,
+            SoundTouchEvent::Reconnecting(1)
+        ));
+        assert!(matches!(
+            next_event(&mut events).await,
+            SoundTouchEvent::Connected
+        ));
+
+        // The missed change is delivered by the resync
+        assert!(matches!(
+            next_event(&mut events).await,
+            SoundTouchEvent::NowPlayingUpdated(_)
+        ));
+        match next_event(&mut events).await {
+            SoundTouchEvent::VolumeUpdated(update) => assert_eq!(update.volume.actual, 33),
+            other => panic!("unexpected event: {:?}", other),
+        }
+        assert!(matches!(
+            next_event(&mut events).await,
+            SoundTouchEvent::BassUpdated(Some(_))
+        ));
+        assert!(matches!(
+            next_event(&mut events).await,
+            SoundTouchEvent::ZoneUpdated(Some(_))
+        ));
+        assert!(matches!(
+            next_event(&mut events).await,
+            SoundTouchEvent::PresetsUpdated(_)
+        ));
+        assert!(matches!(
+            next_event(&mut events).await,
+            SoundTouchEvent::RecentsUpdated(_)
+        ));
+
+        // Updates keep flowing on the new connection
+        controller.set_volume(12).await.unwrap();
+        match next_event(&mut events).await {
+            SoundTouchEvent::VolumeUpdated(update) => assert_eq!(update.volume.actual, 12),
+            other => panic!("unexpected event: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_listener_gives_up_after_max_attempts() {
+        // Reserve a port and release it, so connections are refused
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let mut client = BoseClient::builder("127.0.0.1")
+            .websocket_port(port)
+            .build()
+            .unwrap();
+        let mut events = client.subscribe();
+
+        let result = timeout(
+            Duration::from_secs(5),
+            client.listen_with_reconnect(fast_reconnect().max_attempts(3)),
+        )
+        .await
+        .unwrap();
+        assert!(matches!(result, Err(BoseError::ConnectionError(_))));
+
+        assert!(matches!(events.try_recv(), Ok(SoundTouchEvent::Connecting)));
+        assert!(matches!(
+            events.try_recv(),
+            Ok(SoundTouchEvent::Reconnecting(1))
+        ));
+        assert!(matches!(
+            events.try_recv(),
+            Ok(SoundTouchEvent::Reconnecting(2))
+        ));
+        assert!(events.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_keepalive_detects_dead_connection() {
+        let emulator = start().await;
+        let mut client = emulator.client().unwrap();
+        client.set_ping_interval(Some(Duration::from_millis(20)));
+        client.set_pong_timeout(Duration::from_millis(100));
+        let mut events = client.subscribe();
+        let controller = emulator.client().unwrap();
+        let listener = tokio::spawn(async move { client.connect_and_listen().await });
+
+        wait_for_ws_client(&emulator).await;
+
+        // A healthy connection answers the pings and stays open
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        controller.set_volume(25).await.unwrap();
+        match next_event(&mut events).await {
+            SoundTouchEvent::VolumeUpdated(update) => assert_eq!(update.volume.actual, 25),
+            other => panic!("unexpected event: {:?}", other),
+        }
+
+        emulator.freeze_websockets();
+        let result = timeout(Duration::from_secs(5), listener)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(matches!(result, Err(BoseError::PongTimeout(_))));
+        assert!(matches!(
+            next_event(&mut events).await,
+            SoundTouchEvent::Disconnected
+        ));
+    }
+
+    /// Receives the next item of an event stream
+    async fn next_item(events: &mut EventStream) -> Option<SoundTouchEvent> {
+        timeout(Duration::from_secs(5), events.next())
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_event_stream_from_shared_client() {
+        let emulator = start().await;
+        let client = Arc::new(emulator.client().unwrap());
+        let mut events = client.events();
+        assert!(matches!(
+            next_item(&mut events).await,
+            Some(SoundTouchEvent::Connecting)
+        ));
+        assert!(matches!(
+            next_item(&mut events).await,
+            Some(SoundTouchEvent::Connected)
+        ));
+
+        client.set_volume(17).await.unwrap();
+        match next_item(&mut events).await {
+            Some(SoundTouchEvent::VolumeUpdated(update)) => assert_eq!(update.volume.actual, 17),
+            other => panic!("unexpected event: {:?}", other),
+        }
+
+        // Cancelling closes the connection and ends the stream
+        let handle = events.handle();
+        handle.cancel();
+        assert!(next_item(&mut events).await.is_none());
+        assert!(handle.is_finished());
+        wait_for_ws_disconnect(&emulator).await;
+    }
+
+    #[tokio::test]
+    async fn test_device_state_follows_events() {
+        let emulator = start().await;
+        let client = emulator.client().unwrap();
+        client.set_volume(12).await.unwrap();
+
+        let state = DeviceState::follow(&client).await.unwrap();
+        assert_eq!(state.volume().borrow().actual, 12);
+        assert_eq!(state.info().borrow().device_id, "000C8AB02519");
+        assert!(state.recents().borrow().recent.is_empty());
+        wait_for_ws_client(&emulator).await;
+
+        let mut volume = state.volume();
+        let mut changes = state.changes();
+        client.set_volume(33).await.unwrap();
+        timeout(Duration::from_secs(5), volume.wait_for(|v| v.actual == 33))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            changes.try_recv().unwrap(),
+            StateChange::VolumeChanged {
+                from: 12,
+                to: 33,
+                delta: 21,
+            }
+        );
+
+        // Unchanged values neither count as a change nor wake watchers
+        let unchanged = state.volume().borrow().clone();
+        assert!(!state.apply(SoundTouchEvent::VolumeUpdated(VolumeUpdate {
+            volume: unchanged
+        })));
+        assert!(!volume.has_changed().unwrap());
+        assert!(changes.try_recv().is_err());
+
+        // Bare notifications are resolved over HTTP
+        let mut bass = state.bass();
+        client.set_bass(-5).await.unwrap();
+        timeout(Duration::from_secs(5), bass.wait_for(|b| b.actual == -5))
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(!state.apply(SoundTouchEvent::BassUpdated(None)));
+
+        // Dropping the state stops listening
+        drop(state);
+        wait_for_ws_disconnect(&emulator).await;
+    }
+
+    #[tokio::test]
+    async fn test_presets_follow_reference_device() {
+        let reference = start().await;
+        let target = start().await;
+        let reference_client = reference.client().unwrap();
+        let targets = [target.client().unwrap()];
+        let (tx, mut outcomes) = tokio::sync::mpsc::unbounded_channel();
+        let task = tokio::spawn(async move {
+            keep_presets_in_sync(&reference_client, &targets, |round| {
+                let _ = tx.send(round);
+            })
+            .await
+        });
+
+        let mut next_round = async || {
+            timeout(Duration::from_secs(5), outcomes.recv())
+                .await
+                .unwrap()
+                .unwrap()
+        };
+        let round = next_round().await;
+        assert!(round[0].changed_slots().is_empty());
+        wait_for_ws_client(&reference).await;
+
+        let aux = reference.client().unwrap();
+        let content_item = aux.get_status().await.unwrap().content_item;
+        aux.store_preset(3, &content_item).await.unwrap();
+        let round = next_round().await;
+        assert_eq!(round[0].changed_slots(), vec![3]);
+        assert_eq!(target.state().presets, reference.state().presets);
+
+        task.abort();
+    }
+
+    #[tokio::test]
+    async fn test_session_sends_requests_over_websocket() {
+        let emulator = start().await;
+        let client = emulator.client().unwrap();
+        let session = client.connect_session().await.unwrap();
+        assert_eq!(session.device_id(), "000C8AB02519");
+        let mut events = session.subscribe();
+
+        assert_eq!(session.set_volume(44).await.unwrap().value, "/volume");
+        assert_eq!(session.get_volume().await.unwrap().actual, 44);
+        session
+            .press_and_release_key(&KeyValue::Power)
+            .await
+            .unwrap();
+        assert_eq!(
+            session.get_status().await.unwrap(),
+            client.get_status().await.unwrap()
+        );
+        assert!(emulator.calls().contains(&Call::Key(KeyValue::Power)));
+
+        // Notifications arrive on the same connection
+        match timeout(Duration::from_secs(5), events.recv())
+            .await
+            .unwrap()
+            .unwrap()
+        {
+            SoundTouchEvent::VolumeUpdated(update) => assert_eq!(update.volume.actual, 44),
+            other => panic!("unexpected event: {:?}", other),
+        }
+
+        // Device errors are reported like over HTTP
+        emulator.fail_next(client_xml_error());
+        assert!(matches!(
+            session.set_volume(10).await,
+            Err(BoseError::DeviceError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_session_request_timeout() {
+        let emulator = start().await;
+        let session = emulator.client().unwrap().connect_session().await.unwrap();
+
+        emulator.freeze_websockets();
+        let result = session
+            .request("GET", "volume", None, Duration::from_millis(100))
+            .await;
+        assert!(matches!(result, Err(BoseError::ResponseTimeout(_))));
+        assert_eq!(session.pending_requests(), 0);
+
+        // A request that is dropped while waiting stops waiting too
+        let dropped = timeout(Duration::from_millis(100), session.get_volume()).await;
+        assert!(dropped.is_err());
+        assert_eq!(session.pending_requests(), 0);
+
+        // Stopping the emulator closes the frozen connection
+        drop(emulator);
+        let result = timeout(Duration::from_secs(5), session.get_volume())
+            .await
+            .unwrap();
+        assert!(matches!(result, Err(BoseError::WebSocketClosed)));
+    }
+}
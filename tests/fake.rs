@@ -0,0 +1,125 @@
+#[cfg(feature = "fake")]
+mod tests {
+    use bose_soundtouch::*;
+
+    /// Example of automation code that is generic over the device
+    async fn evening_mode<S: SoundTouchApi>(speaker: &S) -> Result<()> {
+        if speaker.get_status().await?.source == "STANDBY" {
+            speaker.power().await?;
+        }
+        speaker.set_volume(15).await?;
+        speaker.set_bass(-3).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_fake_records_calls_and_updates_state() {
+        let speaker = FakeSoundTouch::new();
+        evening_mode(&speaker).await.unwrap();
+
+        assert_eq!(
+            speaker.calls(),
+            vec![
+                Call::GetStatus,
+                Call::Key(KeyValue::Power),
+                Call::SetVolume(15),
+                Call::SetBass(-3),
+            ]
+        );
+        let state = speaker.state();
+        assert_ne!(state.now_playing.source, "STANDBY");
+        assert_eq!(state.volume.actual, 15);
+        assert_eq!(state.bass.actual, -3);
+    }
+
+    #[tokio::test]
+    async fn test_fake_returns_queued_failures() {
+        let speaker = FakeSoundTouch::new();
+        speaker.fail_next(BoseError::InvalidPreset("boom".to_string()));
+
+        assert!(speaker.get_volume().await.is_err());
+        assert!(speaker.get_volume().await.is_ok());
+        assert_eq!(speaker.calls(), vec![Call::GetVolume, Call::GetVolume]);
+    }
+
+    #[tokio::test]
+    async fn test_fake_swaps_presets() {
+        let speaker = FakeSoundTouch::new();
+        let aux = speaker.get_status().await.unwrap().content_item;
+        speaker.store_preset(1, &aux).await.unwrap();
+        let mut bluetooth = aux.clone();
+        bluetooth.source = Source::Bluetooth;
+        speaker.store_preset(3, &bluetooth).await.unwrap();
+
+        let presets = speaker.swap_presets(1, 3).await.unwrap();
+        assert_eq!(presets.items[0].content_item, bluetooth);
+        assert_eq!(presets.items[1].content_item, aux);
+        assert_eq!(
+            &speaker.calls()[3..],
+            &[
+                Call::GetPresets,
+                Call::StorePreset {
+                    id: 1,
+                    content_item: bluetooth,
+                },
+                Call::StorePreset {
+                    id: 3,
+                    content_item: aux,
+                },
+            ]
+        );
+
+        // Swapping a slot with itself changes nothing
+        speaker.swap_presets(1, 1).await.unwrap();
+        assert_eq!(speaker.calls().last(), Some(&Call::GetPresets));
+    }
+
+    #[tokio::test]
+    async fn test_fake_volume_limits() {
+        let speaker = FakeSoundTouch::new();
+        assert!(matches!(
+            speaker.set_volume(-5).await,
+            Err(BoseError::InvalidVolume(-5))
+        ));
+        assert!(matches!(
+            speaker.set_volume(101).await,
+            Err(BoseError::InvalidVolume(101))
+        ));
+        assert_eq!(speaker.state().volume.actual, 20);
+
+        speaker.update_state(|state| state.max_volume = Some(VolumeLevel::new(25)));
+        speaker.set_volume(60).await.unwrap();
+        assert_eq!(speaker.state().volume.actual, 25);
+        assert_eq!(speaker.volume_down(10).await.unwrap(), VolumeLevel::new(15));
+        assert_eq!(speaker.volume_up(30).await.unwrap(), VolumeLevel::new(25));
+
+        speaker.set_mute(true).await.unwrap();
+        speaker.set_mute(true).await.unwrap();
+        assert_eq!(speaker.state().volume.mute_enabled, Some(true));
+        assert_eq!(
+            &speaker.calls()[speaker.calls().len() - 4..],
+            &[
+                Call::VolumeDown(10),
+                Call::VolumeUp(30),
+                Call::SetMute(true),
+                Call::SetMute(true),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fake_zone_management() {
+        let speaker = FakeSoundTouch::new();
+        speaker
+            .set_zone(&[("192.168.1.144".to_string(), "AABBCCDDEEFF".to_string())])
+            .await
+            .unwrap();
+        assert_eq!(speaker.get_zone().await.unwrap().members.len(), 2);
+
+        speaker
+            .remove_zone_slave("192.168.1.144", "AABBCCDDEEFF")
+            .await
+            .unwrap();
+        assert_eq!(speaker.get_zone().await.unwrap().members.len(), 1);
+    }
+}
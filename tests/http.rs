@@ -14,9 +14,337 @@ fn test_preset_key_serializer() {
     assert_eq!(xml, r#"<key state="press" sender="Gabbo">PRESET_1</key>"#);
 }
 
+#[test]
+fn test_play_pause_key_serializer() {
+    let key = PostKey::release(&KeyValue::PlayPause);
+    let xml = quick_xml::se::to_string(&key).unwrap();
+    assert_eq!(
+        xml,
+        r#"<key state="release" sender="Gabbo">PLAY_PAUSE</key>"#
+    );
+}
+
+#[test]
+fn test_volume_and_invalid_key_serializer() {
+    for (key, name) in [
+        (KeyValue::VolumeUp, "VOLUME_UP"),
+        (KeyValue::VolumeDown, "VOLUME_DOWN"),
+        (KeyValue::InvalidKey, "INVALID_KEY"),
+    ] {
+        let xml = quick_xml::se::to_string(&PostKey::press(&key)).unwrap();
+        assert_eq!(
+            xml,
+            format!(r#"<key state="press" sender="Gabbo">{}</key>"#, name)
+        );
+    }
+}
+
 #[test]
 fn test_volume_serializer() {
     let volume = PostVolume::new(50);
     let xml = quick_xml::se::to_string(&volume).unwrap();
     assert_eq!(xml, r#"<volume>50</volume>"#);
 }
+
+#[test]
+fn test_volume_level() {
+    assert_eq!(VolumeLevel::new(-5), VolumeLevel::MIN);
+    assert_eq!(VolumeLevel::new(250), VolumeLevel::MAX);
+    assert_eq!(VolumeLevel::new(42).value(), 42);
+    assert_eq!(VolumeLevel::new(98).up(5), VolumeLevel::MAX);
+    assert_eq!(VolumeLevel::new(3).down(5), VolumeLevel::MIN);
+    assert_eq!(i32::from(VolumeLevel::try_from(30).unwrap()), 30);
+    assert!(matches!(
+        VolumeLevel::try_from(101),
+        Err(BoseError::InvalidVolume(101))
+    ));
+    assert!(matches!(
+        VolumeLevel::try_from(-1),
+        Err(BoseError::InvalidVolume(-1))
+    ));
+}
+
+#[test]
+fn test_track_info_deserializer() {
+    let track: NowPlaying =
+        quick_xml::de::from_str(include_str!("samples/track_info.xml")).unwrap();
+    assert_eq!(track.source, "TUNEIN");
+    assert_eq!(track.track.as_deref(), Some("Qmusic België"));
+    assert_eq!(track.artist.as_deref(), Some("Teddy Swims - Bad Dreams"));
+    assert!(matches!(track.play_status, Some(PlayStatus::PlayState)));
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_deprecated_content_items_convert() {
+    let track: NowPlaying =
+        quick_xml::de::from_str(include_str!("samples/track_info.xml")).unwrap();
+    let item = track.content_item;
+    assert_eq!(item.item_name(), item.name.as_deref());
+    assert_eq!(item.item_type(), Some(ContentItemType::StationUrl));
+
+    let now_playing = NowPlayingContentItem::from(item.clone());
+    assert_eq!(now_playing.source, "TUNEIN");
+    assert_eq!(now_playing.content_type.as_deref(), Some("stationurl"));
+
+    let preset = PresetContentItem::from(item.clone());
+    assert_eq!(preset.preset_type, "stationurl");
+    assert_eq!(Some(preset.name.clone()), item.name);
+    assert_eq!(ContentItem::from(preset), item);
+}
+
+#[test]
+fn test_shuffle_and_repeat_deserializer() {
+    let now_playing: NowPlaying =
+        quick_xml::de::from_str(include_str!("samples/now_playing_spotify.xml")).unwrap();
+    assert_eq!(now_playing.source, Source::Spotify);
+    assert_eq!(now_playing.shuffle_setting, Some(ShuffleMode::On));
+    assert_eq!(now_playing.repeat_setting, Some(RepeatMode::All));
+    assert!(now_playing.shuffle_setting.unwrap().is_on());
+
+    // Sources without shuffle and repeat leave the settings out
+    let radio: NowPlaying =
+        quick_xml::de::from_str(include_str!("samples/track_info.xml")).unwrap();
+    assert_eq!(radio.shuffle_setting, None);
+    assert_eq!(radio.repeat_setting, None);
+}
+
+#[test]
+fn test_unlisted_source_round_trips() {
+    let now_playing: NowPlaying =
+        quick_xml::de::from_str(include_str!("samples/now_playing_airplay.xml")).unwrap();
+    assert_eq!(now_playing.source, Source::Other("AIRPLAY".to_string()));
+    assert_eq!(now_playing.source, "AIRPLAY");
+
+    let presets: Presets =
+        quick_xml::de::from_str(include_str!("samples/presets_radioplayer.xml")).unwrap();
+    let item = &presets.items[0].content_item;
+    assert_eq!(item.source, Source::Other("RADIOPLAYER".to_string()));
+    assert_eq!(presets.items[0].created_on, 1701220500);
+    assert_eq!(presets.items[1].content_item.source, Source::Tunein);
+
+    let xml = quick_xml::se::to_string(item).unwrap();
+    assert!(xml.contains(r#"source="RADIOPLAYER""#), "{}", xml);
+    assert_eq!("RADIOPLAYER".parse::<Source>().unwrap(), item.source);
+}
+
+#[test]
+fn test_recents_deserializer() {
+    let recents: Recents = quick_xml::de::from_str(include_str!("samples/recents.xml")).unwrap();
+    assert_eq!(recents.recent.len(), 2);
+    let recent = &recents.recent[0];
+    assert_eq!(recent.device_id, "000C8AB02519");
+    assert_eq!(recent.utc_time, 1700000100);
+    assert_eq!(recent.id, None);
+    assert_eq!(recent.content_item.source, Source::Tunein);
+    assert_eq!(
+        recent.content_item.location.as_deref(),
+        Some("/v1/playback/station/s17077")
+    );
+    assert_eq!(recents.recent[1].content_item.source, Source::Bluetooth);
+
+    // The shape documented in the API reference has no id attribute
+    let documented: Recents =
+        quick_xml::de::from_str(include_str!("samples/recents_documented.xml")).unwrap();
+    let recent = &documented.recent[0];
+    assert_eq!(recent.device_id, "D05FB8A9591D");
+    assert_eq!(recent.utc_time, 1488201498);
+    assert_eq!(recent.id, None);
+    assert_eq!(recent.content_item.name.as_deref(), Some("K-LOVE Radio"));
+
+    let with_id: Recent = quick_xml::de::from_str(
+        r#"<recent deviceID="D05FB8A9591D" utcTime="1488201498" id="2487503041"><contentItem source="AUX" sourceAccount="AUX" isPresetable="false" /></recent>"#,
+    )
+    .unwrap();
+    assert_eq!(with_id.id.as_deref(), Some("2487503041"));
+
+    let empty: Recents = quick_xml::de::from_str("<recents />").unwrap();
+    assert!(empty.recent.is_empty());
+}
+
+#[test]
+fn test_device_errors_deserializer() {
+    let errors: DeviceErrors = quick_xml::de::from_str(include_str!("samples/errors.xml")).unwrap();
+    assert_eq!(errors.device_id.as_deref(), Some("D05FB8A9591D"));
+    assert_eq!(
+        errors.errors,
+        vec![DeviceError {
+            value: 1019,
+            name: "CLIENT_XML_ERROR".to_string(),
+            severity: "Unknown".to_string(),
+            message: "1019".to_string(),
+        }]
+    );
+    let error = BoseError::DeviceError(errors);
+    assert_eq!(
+        error.to_string(),
+        "Device returned an error: 1019 CLIENT_XML_ERROR (Unknown): 1019"
+    );
+}
+
+/// Serves a single canned HTTP response on a random local port
+fn serve_once(status: &'static str, body: &'static str) -> u16 {
+    serve_sequence(vec![(status, body)])
+}
+
+/// Serves the given HTTP responses, one per connection, on a random local port
+fn serve_sequence(responses: Vec<(&'static str, &'static str)>) -> u16 {
+    use std::io::{Read, Write};
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    std::thread::spawn(move || {
+        for (status, body) in responses {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0u8; 4096];
+            let _ = stream.read(&mut buf);
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Type: text/xml\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+        }
+    });
+    port
+}
+
+fn local_client(port: u16) -> BoseClient {
+    BoseClient::builder("127.0.0.1")
+        .port(port)
+        .timeout(std::time::Duration::from_secs(5))
+        .retry_policy(RetryPolicy::new(3).initial_backoff(std::time::Duration::from_millis(1)))
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_builder_uses_custom_port() {
+    let port = serve_once("200 OK", VOLUME_RESPONSE);
+    let client = local_client(port);
+    assert_eq!(client.port(), port);
+    let volume = client.get_volume().await.unwrap();
+    assert_eq!(volume.actual, 12);
+}
+
+#[tokio::test]
+async fn test_http_status_is_reported() {
+    let port = serve_once("404 Not Found", "not found");
+    let result = local_client(port).get_volume().await;
+    match result {
+        Err(BoseError::HttpStatus { status, url, body }) => {
+            assert_eq!(status, 404);
+            assert_eq!(url, format!("http://127.0.0.1:{}/volume", port));
+            assert_eq!(body, "not found");
+        }
+        other => panic!("Expected HttpStatus error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_http_status_body_is_truncated() {
+    let body: &'static str = Box::leak("x".repeat(1000).into_boxed_str());
+    let port = serve_once("500 Internal Server Error", body);
+    let mut client = local_client(port);
+    client.set_retry_policy(RetryPolicy::new(1));
+    match client.set_volume(50).await {
+        Err(BoseError::HttpStatus { status, body, .. }) => {
+            assert_eq!(status, 500);
+            assert_eq!(body, "x".repeat(256));
+        }
+        other => panic!("Expected HttpStatus error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_device_error_is_reported_for_post() {
+    let port = serve_once("200 OK", include_str!("samples/errors.xml"));
+    let result = local_client(port).set_volume(50).await;
+    match result {
+        Err(BoseError::DeviceError(errors)) => {
+            assert_eq!(errors.errors[0].name, "CLIENT_XML_ERROR");
+        }
+        other => panic!("Expected DeviceError, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_status_is_returned_for_post() {
+    let port = serve_once("200 OK", "<status>/volume</status>");
+    let status = local_client(port).set_volume(50).await.unwrap();
+    assert_eq!(status.value, "/volume");
+}
+
+#[tokio::test]
+async fn test_status_is_returned_for_key_release() {
+    let port = serve_sequence(vec![
+        ("200 OK", "<status>/key</status>"),
+        ("200 OK", "<status>/key</status>"),
+    ]);
+    let status = local_client(port).play().await.unwrap();
+    assert_eq!(status.value, "/key");
+}
+
+#[tokio::test]
+async fn test_get_recents() {
+    let port = serve_once("200 OK", include_str!("samples/recents.xml"));
+    let recents = local_client(port).get_recents().await.unwrap();
+    assert_eq!(
+        recents.recent[0].content_item.name.as_deref(),
+        Some("Qmusic België")
+    );
+}
+
+const VOLUME_RESPONSE: &str = r#"<volume deviceID="000C8AB02519"><targetvolume>12</targetvolume><actualvolume>12</actualvolume><muteenabled>false</muteenabled></volume>"#;
+
+#[tokio::test]
+async fn test_get_is_retried_on_transient_error() {
+    let port = serve_sequence(vec![
+        ("503 Service Unavailable", ""),
+        ("200 OK", VOLUME_RESPONSE),
+    ]);
+    let volume = local_client(port).get_volume().await.unwrap();
+    assert_eq!(volume.actual, 12);
+}
+
+#[tokio::test]
+async fn test_post_is_not_retried_by_default() {
+    let port = serve_sequence(vec![
+        ("503 Service Unavailable", ""),
+        ("200 OK", "<status>/volume</status>"),
+    ]);
+    let result = local_client(port).set_volume(50).await;
+    assert!(matches!(
+        result,
+        Err(BoseError::HttpStatus { status: 503, .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_post_is_retried_when_requested() {
+    let port = serve_sequence(vec![
+        ("503 Service Unavailable", ""),
+        ("200 OK", "<status>/volume</status>"),
+    ]);
+    let mut client = local_client(port);
+    client.set_retry_policy(
+        RetryPolicy::new(2)
+            .initial_backoff(std::time::Duration::from_millis(1))
+            .retry_non_idempotent(true),
+    );
+    client.set_volume(50).await.unwrap();
+}
+
+#[test]
+fn test_backoff_is_capped() {
+    let backoff = Backoff {
+        initial: std::time::Duration::from_millis(100),
+        max: std::time::Duration::from_millis(250),
+        multiplier: 2.0,
+        jitter: false,
+    };
+    assert_eq!(backoff.delay(1), std::time::Duration::from_millis(100));
+    assert_eq!(backoff.delay(2), std::time::Duration::from_millis(200));
+    assert_eq!(backoff.delay(3), std::time::Duration::from_millis(250));
+}
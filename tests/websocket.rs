@@ -17,13 +17,21 @@ mod tests {
         </nowPlayingUpdated>
     </updates>"#;
 
+    const SAMPLE_BATCHED: &str = include_str!("samples/batched_updates.xml");
+
+    /// Parses a message that is expected to carry a single event
+    fn parse_single(client: &BoseClient, xml: &str) -> Result<SoundTouchEvent> {
+        let mut events = client.parse_events(xml)?;
+        assert_eq!(events.len(), 1, "Expected a single event: {:?}", events);
+        Ok(events.remove(0))
+    }
+
     #[test]
     fn test_parse_now_playing() {
         let mut client = BoseClient::new_from_string("test");
         let _rx = client.subscribe();
-        let event = client
-            .parse_event(SAMPLE_NOW_PLAYING)
-            .expect("Failed to parse now playing event");
+        let event =
+            parse_single(&client, SAMPLE_NOW_PLAYING).expect("Failed to parse now playing event");
         match event {
             SoundTouchEvent::NowPlayingUpdated(update) => {
                 assert_eq!(update.now_playing.source, Source::Tunein);
@@ -37,30 +45,283 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_events_share_the_model_derives() {
+        fn assert_model<T: Clone + PartialEq + serde::Serialize>() {}
+        assert_model::<SoundTouchEvent>();
+        assert_model::<VolumeUpdate>();
+        assert_model::<NowPlayingUpdate>();
+
+        let mut client = BoseClient::new_from_string("test");
+        let _rx = client.subscribe();
+        let events = client.parse_events(SAMPLE_BATCHED).unwrap();
+        assert_eq!(events, client.parse_events(SAMPLE_BATCHED).unwrap());
+        assert_ne!(events[0], events[1]);
+    }
+
+    #[test]
+    fn test_now_playing_matches_http_model() {
+        let mut client = BoseClient::new_from_string("test");
+        let _rx = client.subscribe();
+        let http: NowPlaying =
+            quick_xml::de::from_str(include_str!("samples/track_info.xml")).unwrap();
+        match parse_single(&client, SAMPLE_NOW_PLAYING).unwrap() {
+            SoundTouchEvent::NowPlayingUpdated(update) => {
+                assert_eq!(update.now_playing, http);
+                assert_eq!(
+                    update.now_playing.art_url(),
+                    Some("http://cdn-albums.tunein.com/gn/28XH6M1Q18g.jpg")
+                );
+                assert_eq!(
+                    update.now_playing.stream_type,
+                    Some(StreamType::RadioStreaming)
+                );
+            }
+            _ => panic!("Expected NowPlayingUpdated event"),
+        }
+    }
+
+    #[test]
+    fn test_parse_batched_updates() {
+        let client = BoseClient::new_from_string("test");
+        let events = client
+            .parse_events(SAMPLE_BATCHED)
+            .expect("Failed to parse batched updates");
+        assert_eq!(events.len(), 2);
+        assert!(
+            matches!(&events[0], SoundTouchEvent::VolumeUpdated(update) if update.volume.actual == 12)
+        );
+        assert!(matches!(
+            &events[1],
+            SoundTouchEvent::NowPlayingUpdated(update) if update.now_playing.source == Source::Bluetooth
+        ));
+    }
+
+    #[test]
+    fn test_parse_repeated_update_kind() {
+        let client = BoseClient::new_from_string("test");
+        let events = client
+            .parse_events(
+                r#"<updates deviceID="000C8AB02519"><volumeUpdated><volume><targetvolume>12</targetvolume><actualvolume>12</actualvolume><muteenabled>false</muteenabled></volume></volumeUpdated><volumeUpdated><volume><targetvolume>14</targetvolume><actualvolume>14</actualvolume><muteenabled>false</muteenabled></volume></volumeUpdated></updates>"#,
+            )
+            .expect("Failed to parse repeated updates");
+        let volumes: Vec<i32> = events
+            .iter()
+            .map(|event| match event {
+                SoundTouchEvent::VolumeUpdated(update) => update.volume.actual,
+                other => panic!("Expected VolumeUpdated event, got {:?}", other),
+            })
+            .collect();
+        assert_eq!(volumes, vec![12, 14]);
+    }
+
+    #[test]
+    fn test_parse_updates_in_document_order() {
+        let client = BoseClient::new_from_string("test");
+        let events = client
+            .parse_events(
+                r#"<updates deviceID="000C8AB02519"><zoneUpdated /><unknownUpdated /><volumeUpdated /></updates>"#,
+            )
+            .expect("Failed to parse updates");
+        assert!(matches!(
+            events.as_slice(),
+            [
+                SoundTouchEvent::ZoneUpdated(None),
+                SoundTouchEvent::VolumeChangeNotified
+            ]
+        ));
+    }
+
+    #[test]
+    fn test_parse_documented_notifications() {
+        let client = BoseClient::new_from_string("test");
+        type Check = fn(&SoundTouchEvent) -> bool;
+        let cases: &[(&str, Check)] = &[
+            (include_str!("samples/volume_signal.xml"), |e| {
+                matches!(e, SoundTouchEvent::VolumeChangeNotified)
+            }),
+            (include_str!("samples/now_playing_signal.xml"), |e| {
+                matches!(e, SoundTouchEvent::NowPlayingChangeNotified)
+            }),
+            (include_str!("samples/presets_updated.xml"), |e| {
+                matches!(e, SoundTouchEvent::PresetsUpdated(_))
+            }),
+            (include_str!("samples/acct_mode_updated.xml"), |e| {
+                matches!(e, SoundTouchEvent::AccountModeUpdated)
+            }),
+            (
+                include_str!("samples/error_notification.xml"),
+                |e| matches!(e, SoundTouchEvent::ErrorNotification(errors) if errors.errors[0].value == 1005),
+            ),
+            (include_str!("samples/bass_updated.xml"), |e| {
+                matches!(e, SoundTouchEvent::BassUpdated(None))
+            }),
+            (
+                include_str!("samples/zone_updated.xml"),
+                |e| matches!(e, SoundTouchEvent::ZoneUpdated(Some(zone)) if zone.members.len() == 1),
+            ),
+            (include_str!("samples/sw_update_status_updated.xml"), |e| {
+                matches!(e, SoundTouchEvent::SoftwareUpdateStatusUpdated)
+            }),
+            (
+                include_str!("samples/site_survey_results_updated.xml"),
+                |e| matches!(e, SoundTouchEvent::SiteSurveyResultsUpdated),
+            ),
+            (include_str!("samples/sources_updated.xml"), |e| {
+                matches!(e, SoundTouchEvent::SourcesUpdated(None))
+            }),
+            (include_str!("samples/now_selection_updated.xml"), |e| {
+                matches!(e, SoundTouchEvent::PresetSelected(_))
+            }),
+            (include_str!("samples/info_updated.xml"), |e| {
+                matches!(e, SoundTouchEvent::InfoUpdated(None))
+            }),
+        ];
+        for (xml, expected) in cases {
+            let event = parse_single(&client, xml)
+                .unwrap_or_else(|e| panic!("Failed to parse {}: {}", xml, e));
+            assert!(expected(&event), "Unexpected event {:?} for {}", event, xml);
+        }
+    }
+
+    #[test]
+    fn test_parse_presets_updated() {
+        let client = BoseClient::new_from_string("test");
+        match parse_single(&client, include_str!("samples/presets_updated.xml")).unwrap() {
+            SoundTouchEvent::PresetsUpdated(presets) => {
+                assert_eq!(presets.items.len(), 2);
+                assert_eq!(presets.items[0].id, 1);
+                assert_eq!(presets.items[0].created_on, 1701220500);
+                assert_eq!(presets.items[0].content_item.source, Source::Tunein);
+                assert_eq!(
+                    presets.items[1].content_item.name.as_deref(),
+                    Some("Radio 1")
+                );
+            }
+            other => panic!("Expected PresetsUpdated event, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_recents_updated() {
+        let client = BoseClient::new_from_string("test");
+        match parse_single(&client, include_str!("samples/recents_updated.xml")).unwrap() {
+            SoundTouchEvent::RecentsUpdated(update) => {
+                let recent = &update.recents.recent[0];
+                assert_eq!(recent.device_id, "000C8AB02519");
+                assert_eq!(recent.utc_time, 1700000100);
+                assert_eq!(recent.content_item.source, Source::Tunein);
+            }
+            other => panic!("Expected RecentsUpdated event, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_now_selection_updated() {
+        let client = BoseClient::new_from_string("test");
+        match parse_single(&client, include_str!("samples/now_selection_updated.xml")).unwrap() {
+            SoundTouchEvent::PresetSelected(preset) => {
+                assert_eq!(preset.id, 2);
+                assert_eq!(preset.content_item.source, Source::InternetRadio);
+                assert_eq!(preset.content_item.location.as_deref(), Some("4712"));
+            }
+            other => panic!("Expected PresetSelected event, got {:?}", other),
+        }
+    }
+
     #[test]
     fn test_parse_volume() {
         let mut client = BoseClient::new_from_string("test");
         let _rx = client.subscribe();
-        let event = client
-            .parse_event(SAMPLE_VOLUME)
-            .expect("Failed to parse volume event");
+        let event = parse_single(&client, SAMPLE_VOLUME).expect("Failed to parse volume event");
         match event {
             SoundTouchEvent::VolumeUpdated(update) => {
-                assert_eq!(update.volume.target_volume, 5);
-                assert_eq!(update.volume.actual_volume, 5);
-                assert!(!update.volume.mute_enabled);
+                assert_eq!(update.volume.target, 5);
+                assert_eq!(update.volume.actual, 5);
+                assert_eq!(update.volume.mute_enabled, Some(false));
             }
             _ => panic!("Expected VolumeUpdated event"),
         }
     }
 
+    #[test]
+    fn test_now_playing_changes() {
+        let playing: NowPlaying =
+            quick_xml::de::from_str(include_str!("samples/track_info.xml")).unwrap();
+        assert!(StateChange::now_playing(&playing, &playing).is_empty());
+
+        let mut paused = playing.clone();
+        paused.play_status = Some(PlayStatus::PauseState);
+        assert_eq!(
+            StateChange::now_playing(&playing, &paused),
+            vec![StateChange::PlayStatusChanged {
+                from: Some(PlayStatus::PlayState),
+                to: Some(PlayStatus::PauseState),
+            }]
+        );
+
+        let mut next = playing.clone();
+        next.source = Source::Bluetooth;
+        next.track = Some("Next Track".to_string());
+        let changes = StateChange::now_playing(&playing, &next);
+        assert_eq!(changes.len(), 2);
+        assert_eq!(
+            changes[0],
+            StateChange::SourceChanged {
+                from: Source::Tunein,
+                to: Source::Bluetooth,
+            }
+        );
+        match &changes[1] {
+            StateChange::TrackChanged { from, to } => {
+                assert_eq!(from.track.as_deref(), Some("Qmusic België"));
+                assert_eq!(to.track.as_deref(), Some("Next Track"));
+                assert_eq!(from.artist, to.artist);
+            }
+            other => panic!("unexpected change: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_volume_changes() {
+        let mut client = BoseClient::new_from_string("test");
+        let _rx = client.subscribe();
+        let before = match parse_single(&client, SAMPLE_VOLUME).unwrap() {
+            SoundTouchEvent::VolumeUpdated(update) => update.volume,
+            other => panic!("unexpected event: {:?}", other),
+        };
+        let after = Volume {
+            target: 12,
+            actual: 12,
+            mute_enabled: Some(true),
+        };
+        assert_eq!(
+            StateChange::volume(&before, &after),
+            vec![
+                StateChange::MuteChanged(true),
+                StateChange::VolumeChanged {
+                    from: 5,
+                    to: 12,
+                    delta: 7,
+                },
+            ]
+        );
+        let unmuted = Volume {
+            mute_enabled: None,
+            ..after.clone()
+        };
+        assert_eq!(
+            StateChange::volume(&after, &unmuted),
+            vec![StateChange::MuteChanged(false)]
+        );
+    }
+
     #[test]
     fn test_parse_connection() {
         let mut client = BoseClient::new_from_string("test");
         let _rx = client.subscribe();
-        let event = client
-            .parse_event(SAMPLE_CONNECTION)
-            .expect("Failed to parse connection event");
+        let event =
+            parse_single(&client, SAMPLE_CONNECTION).expect("Failed to parse connection event");
         match event {
             SoundTouchEvent::ConnectionStateUpdated(state) => {
                 assert_eq!(state.state, ConnectionStateType::NetworkWifiConnected);
@@ -71,37 +332,20 @@ mod tests {
         }
     }
 
-    #[cfg(feature = "unknown-variants")]
     #[test]
     fn test_parse_unknown_values() {
         let mut client = BoseClient::new_from_string("test");
         let _rx = client.subscribe();
 
-        let event = client
-            .parse_event(SAMPLE_UNKNOWN_SOURCE)
-            .expect("Failed to parse unknown source");
+        let event =
+            parse_single(&client, SAMPLE_UNKNOWN_SOURCE).expect("Failed to parse unknown source");
         match event {
             SoundTouchEvent::NowPlayingUpdated(update) => {
-                assert!(matches!(update.now_playing.source, Source::Unknown));
-                assert!(matches!(
-                    update.now_playing.content_item.source,
-                    Source::Unknown
-                ));
+                let unknown = Source::Other("NEW_SOURCE".to_string());
+                assert_eq!(update.now_playing.source, unknown);
+                assert_eq!(update.now_playing.content_item.source, unknown);
             }
             _ => panic!("Expected NowPlayingUpdated event"),
         }
     }
-
-    #[cfg(not(feature = "unknown-variants"))]
-    #[test]
-    fn test_reject_unknown_values() {
-        let mut client = BoseClient::new_from_string("test");
-        let _rx = client.subscribe();
-
-        let result = client.parse_event(SAMPLE_UNKNOWN_SOURCE);
-        assert!(
-            result.is_err(),
-            "Should fail to parse unknown source when unknown-variants feature is disabled"
-        );
-    }
 }